@@ -0,0 +1,39 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+// With the `embedded-input` feature, this bakes every input found in the input directory (input/<year>/input<day>.txt)
+// into the binary, as a table that `src/embedded.rs` includes. A day with no input file just isn't in the table,
+// rather than stopping the build. Without the feature the table is empty.
+fn main() {
+  println!("cargo:rerun-if-changed=input");
+  let mut entries = vec![];
+  if env::var_os("CARGO_FEATURE_EMBEDDED_INPUT").is_some() {
+    let input_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("input");
+    for year_entry in fs::read_dir(&input_dir).into_iter().flatten().flatten() {
+      // anything else in there (like the input cache) isn't a year
+      let year = match year_entry.file_name().to_str().and_then(|name| name.parse::<u16>().ok()) {
+        Some(year) => year,
+        None => continue,
+      };
+      println!("cargo:rerun-if-changed={}", year_entry.path().display());
+      for day_entry in fs::read_dir(year_entry.path()).into_iter().flatten().flatten() {
+        let file_name = day_entry.file_name();
+        let day = file_name.to_str().and_then(|name| name.strip_prefix("input")?.strip_suffix(".txt")?.parse::<u8>().ok());
+        if let Some(day) = day {
+          println!("cargo:rerun-if-changed={}", day_entry.path().display());
+          entries.push((year, day, day_entry.path()));
+        }
+      }
+    }
+  }
+  entries.sort();
+
+  let mut table = String::from("pub const EMBEDDED_INPUTS: &[(u16, u8, &str)] = &[\n");
+  for (year, day, path) in entries {
+    table.push_str(&format!("  ({}, {}, include_str!({:?})),\n", year, day, path.display().to_string()));
+  }
+  table.push_str("];\n");
+  let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("embedded_inputs.rs");
+  fs::write(out_path, table).unwrap();
+}
@@ -0,0 +1,129 @@
+use crate::solutions::{Day, Params, ASSUMPTION_FAILED};
+use crate::worker::{self, Outcome};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Runs one part of one day over every file in a directory - for example everyone's personal inputs - and prints
+// a table of the results. Each input is solved in its own worker process (so a panic or an infinite loop only
+// affects that one input), with several running at once.
+
+// what came of solving one input - the report (see `report`) is made from these too
+pub struct BatchResult {
+  pub name: String,
+  pub answer: Option<String>,
+  pub time: Duration,
+  pub failed_assumption: Option<String>,
+  pub error: Option<String>,
+}
+
+impl BatchResult {
+  // what went wrong, if anything did
+  pub fn problem(&self) -> Option<String> {
+    match (&self.failed_assumption, &self.error) {
+      (Some(assumption), _) => Some(format!("failed assumption: {}", assumption)),
+      (None, Some(error)) => Some(format!("error: {}", error)),
+      (None, None) => None,
+    }
+  }
+}
+
+fn run_one(day: &Day, part: u8, params: &Params, path: &Path, timeout: Duration) -> BatchResult {
+  let name = path.file_name().unwrap().to_string_lossy().into_owned();
+  match fs::read_to_string(path) {
+    Ok(input) => solve_in_worker(day, part, params, name, &input, timeout),
+    Err(error) => BatchResult {
+      name,
+      answer: None,
+      time: Duration::ZERO,
+      failed_assumption: None,
+      error: Some(format!("couldn't read the file: {}", error)),
+    },
+  }
+}
+
+// solves one part of a day for the input given, in a worker process of its own
+pub fn solve_in_worker(day: &Day, part: u8, params: &Params, name: String, input: &str, timeout: Duration) -> BatchResult {
+  let mut result = BatchResult { name, answer: None, time: Duration::ZERO, failed_assumption: None, error: None };
+  let year = day.year.to_string();
+  let number = day.number.to_string();
+  let part = part.to_string();
+  let param_args = params.to_args();
+  let mut args = vec!["solve-worker", "--year", &year, "--day", &number, "--part", &part];
+  args.extend(param_args.iter().map(|arg| arg.as_str()));
+
+  let start = Instant::now();
+  let outcome = worker::run_in_worker(&args, input, timeout);
+  result.time = start.elapsed();
+  match outcome {
+    Outcome::Answer(answer) => result.answer = Some(answer),
+    Outcome::Panicked { message, .. } => match message.strip_prefix(ASSUMPTION_FAILED) {
+      Some(assumption) => result.failed_assumption = Some(assumption.to_owned()),
+      None => result.error = Some(message),
+    },
+    Outcome::TimedOut => result.error = Some(format!("timed out after {}ms", timeout.as_millis())),
+  }
+  result
+}
+
+fn print_table(results: &[BatchResult]) {
+  let rows: Vec<[String; 4]> = results
+    .iter()
+    .map(|result| {
+      let problem = result.problem().unwrap_or_default();
+      let time = format!("{:.1}ms", result.time.as_secs_f64() * 1000.0);
+      [result.name.clone(), result.answer.clone().unwrap_or(String::from("-")), time, problem]
+    })
+    .collect();
+
+  let headings = ["input", "answer", "time", "problem"];
+  let widths: Vec<usize> = (0..4).map(|column| rows.iter().map(|row| row[column].len()).chain([headings[column].len()]).max().unwrap()).collect();
+  let format_row = |cells: [&str; 4]| -> String {
+    // answers and times are right-aligned, like numbers usually are
+    let line = format!("{:<w0$}  {:>w1$}  {:>w2$}  {}", cells[0], cells[1], cells[2], cells[3], w0 = widths[0], w1 = widths[1], w2 = widths[2]);
+    line.trim_end().to_owned()
+  };
+  println!("{}", format_row(headings));
+  println!("{}", format_row(["-".repeat(widths[0]).as_str(), &"-".repeat(widths[1]), &"-".repeat(widths[2]), &"-".repeat(widths[3])]));
+  for row in rows.iter() {
+    println!("{}", format_row([&row[0], &row[1], &row[2], &row[3]]));
+  }
+}
+
+// entry point for the `batch` command. Returns whether every input was solved without any problems.
+pub fn run(day: &Day, part: u8, params: &Params, dir: &Path, jobs: usize, timeout: Duration) -> Result<bool, String> {
+  if day.part(part).is_none() {
+    return Err(format!("day {} has no part {}", day.number, part));
+  }
+  let entries = fs::read_dir(dir).map_err(|error| format!("couldn't read {}: {}", dir.display(), error))?;
+  let mut paths: Vec<_> = entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).filter(|path| path.is_file()).collect();
+  paths.sort();
+  if paths.is_empty() {
+    return Err(format!("there are no inputs in {}", dir.display()));
+  }
+
+  // each thread keeps taking the next input that hasn't been started on, until there are none left
+  let next = AtomicUsize::new(0);
+  let results = Mutex::new(vec![]);
+  thread::scope(|scope| {
+    for _ in 0..jobs.max(1).min(paths.len()) {
+      scope.spawn(|| loop {
+        let index = next.fetch_add(1, Ordering::SeqCst);
+        if index >= paths.len() {
+          break;
+        }
+        let result = run_one(day, part, params, &paths[index], timeout);
+        results.lock().unwrap().push((index, result));
+      });
+    }
+  });
+
+  let mut results = results.into_inner().unwrap();
+  results.sort_by_key(|(index, _)| *index);
+  let results: Vec<BatchResult> = results.into_iter().map(|(_, result)| result).collect();
+  print_table(&results);
+  Ok(results.iter().all(|result| result.answer.is_some()))
+}
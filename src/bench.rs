@@ -0,0 +1,100 @@
+use crate::profile::{self, Allocations};
+use crate::solutions::{Day, Params, Solver};
+use std::time::{Duration, Instant};
+
+// Times each part of a day over a few runs, and reports what else it cost: the allocations it made (when they're
+// being counted - see `profile`) and whatever counters the solution keeps itself. Unlike the batch command this
+// runs everything in this process, as that's where the allocations and counters are.
+
+struct PartReport {
+  answer: String,
+  times: Vec<Duration>,
+  allocations: Option<Allocations>,
+  counters: Vec<(&'static str, u64)>,
+}
+
+fn bench_part(solver: Solver, input: &str, params: &Params, runs: usize) -> PartReport {
+  let mut report = PartReport { answer: String::new(), times: vec![], allocations: None, counters: vec![] };
+  for run in 0..runs.max(1) {
+    profile::start_counting();
+    let before = profile::allocations();
+    let start = Instant::now();
+    let answer = solver(input, params);
+    report.times.push(start.elapsed());
+    let after = profile::allocations();
+    let counters = profile::stop_counting();
+    // the counts come out the same every run (random choices aside), so the first run's are the ones reported
+    if run == 0 {
+      report.answer = answer;
+      report.allocations = before.zip(after).map(|(before, after)| Allocations {
+        count: after.count - before.count,
+        bytes: after.bytes - before.bytes,
+      });
+      report.counters = counters;
+    }
+  }
+  report
+}
+
+// eg. 1234567 as "1,234,567"
+fn with_commas(number: u64) -> String {
+  let digits = number.to_string();
+  let mut result = String::new();
+  for (index, digit) in digits.chars().enumerate() {
+    if index > 0 && (digits.len() - index).is_multiple_of(3) {
+      result.push(',');
+    }
+    result.push(digit);
+  }
+  result
+}
+
+fn format_bytes(bytes: u64) -> String {
+  let units = ["bytes", "KB", "MB", "GB"];
+  let mut value = bytes as f64;
+  let mut unit = 0;
+  while value >= 1024.0 && unit < units.len() - 1 {
+    value /= 1024.0;
+    unit += 1;
+  }
+  if unit == 0 {
+    format!("{} bytes", bytes)
+  } else {
+    format!("{:.1} {}", value, units[unit])
+  }
+}
+
+fn print_report(day: &Day, part: u8, report: &PartReport) {
+  let best = report.times.iter().min().unwrap();
+  let mean = report.times.iter().sum::<Duration>() / report.times.len() as u32;
+  let runs = if report.times.len() == 1 { String::from("1 run") } else { format!("{} runs", report.times.len()) };
+  let mut rows = vec![(String::from("time"), format!("best {:.1}ms, mean {:.1}ms ({})", best.as_secs_f64() * 1000.0, mean.as_secs_f64() * 1000.0, runs))];
+  rows.push((String::from("allocations"), match report.allocations {
+    Some(Allocations { count, bytes }) => format!("{} ({})", with_commas(count), format_bytes(bytes)),
+    None => String::from("not counted (build with the count-allocations feature)"),
+  }));
+  for (name, value) in report.counters.iter() {
+    rows.push((name.to_string(), with_commas(*value)));
+  }
+
+  println!("day {}, part {}: {}", day.number, part, report.answer);
+  let width = rows.iter().map(|(label, _)| label.len()).max().unwrap();
+  for (label, value) in rows {
+    println!("  {:<width$}  {}", label, value, width = width);
+  }
+}
+
+// entry point for the `bench` command: benchmarks one part, or both, of a day on the input given
+pub fn run(day: &Day, part: Option<u8>, input: &str, params: &Params, runs: usize) -> Result<(), String> {
+  let parts = match part {
+    Some(part) if day.part(part).is_none() => return Err(format!("day {} has no part {}", day.number, part)),
+    Some(part) => vec![part],
+    None => vec![1, 2],
+  };
+  for part in parts {
+    if let Some(solver) = day.part(part) {
+      print_report(day, part, &bench_part(solver, input, params, runs));
+    }
+  }
+  Ok(())
+}
@@ -0,0 +1,435 @@
+use crate::solutions::year2023::*;
+use crate::worker::{self, Outcome};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::io::{self, Read};
+use std::time::Duration;
+
+// Differential testing: several days have a slow approach which is obviously correct, alongside the fast one
+// that's actually needed for the real input. Here we run both on lots of small randomly-generated inputs, and
+// report any input where they don't agree - after first shrinking it down as far as possible, because a
+// 3-line counterexample is a lot more use than a 30-line one.
+//
+// Each case is only included if its day is in the build.
+
+pub struct DiffCase {
+  pub year: u16,
+  pub day: u8,
+  pub part: u8,
+  // makes a random input, in the same text format as the real puzzle input. This should only produce inputs
+  // satisfying whatever assumptions the real input is known to satisfy - otherwise we just find
+  // "disagreements" that are really nothing more than invalid input.
+  pub generate: fn(&mut StdRng) -> String,
+  // gives smaller inputs to try in place of the one given, in rough order of how much smaller they are
+  pub shrink: fn(&str) -> Vec<String>,
+  pub reference: fn(&str) -> String,
+  pub optimized: fn(&str) -> String,
+}
+
+impl DiffCase {
+  pub fn name(&self) -> String {
+    format!("{} day {}, part {}", self.year, self.day, self.part)
+  }
+}
+
+pub struct Disagreement {
+  pub case: String,
+  pub input: String,
+  pub reference: String,
+  pub optimized: String,
+}
+
+// each solver is run in its own process - see the worker module
+fn run_solver(case_index: usize, solver: &str, input: &str, timeout: Duration) -> Outcome {
+  worker::run_in_worker(&["difftest-worker", "--case", &case_index.to_string(), "--solver", solver], input, timeout)
+}
+
+// the hidden `difftest-worker` command: reads an input from stdin and prints what one of the solvers gives
+pub fn run_worker(case_index: usize, solver: &str) {
+  let mut input = String::new();
+  io::stdin().read_to_string(&mut input).unwrap();
+  let case = &all_cases()[case_index];
+  let solver = if solver == "reference" { case.reference } else { case.optimized };
+  print!("{}", solver(&input));
+}
+
+// Returns the two outcomes if they are "different" in the sense we care about. If neither solver manages to
+// give an answer we count that as agreement - it just means the input was bad for both.
+pub fn compare(case_index: usize, input: &str, timeout: Duration) -> Option<(Outcome, Outcome)> {
+  let reference = run_solver(case_index, "reference", input, timeout);
+  let optimized = run_solver(case_index, "optimized", input, timeout);
+  match (&reference, &optimized) {
+    (Outcome::Answer(_), _) | (_, Outcome::Answer(_)) if reference != optimized => Some((reference, optimized)),
+    _ => None,
+  }
+}
+
+// greedy shrinking: keep taking the first smaller input that still shows a disagreement (which `disagrees` gives
+// the outcomes of, if there is one), until none of the candidates do. The outcomes are passed along with the input
+// rather than worked out again at the end, as a solver that isn't deterministic might not disagree a second time.
+fn shrink<T>(case: &DiffCase, input: String, outcomes: T, disagrees: impl Fn(&str) -> Option<T>) -> (String, T) {
+  let mut current = (input, outcomes);
+  'outer: loop {
+    for candidate in (case.shrink)(&current.0) {
+      if let Some(outcomes) = disagrees(&candidate) {
+        current = (candidate, outcomes);
+        continue 'outer;
+      }
+    }
+    return current;
+  }
+}
+
+// finds the case testing a particular day and part, if there is one
+pub fn find_case(year: u16, day: u8, part: u8) -> Option<usize> {
+  all_cases().iter().position(|case| case.year == year && case.day == day && case.part == part)
+}
+
+// runs a single case on `runs` random inputs and returns the (shrunk) first disagreement, if any
+pub fn run_case(case_index: usize, runs: usize, seed: u64, timeout: Duration) -> Option<Disagreement> {
+  let case = &all_cases()[case_index];
+  let mut rng = StdRng::seed_from_u64(seed);
+
+  for _ in 0..runs {
+    let input = (case.generate)(&mut rng);
+    if let Some(outcomes) = compare(case_index, &input, timeout) {
+      let (input, (reference, optimized)) = shrink(case, input, outcomes, |candidate| compare(case_index, candidate, timeout));
+      return Some(Disagreement { case: case.name(), input, reference: reference.describe(), optimized: optimized.describe() });
+    }
+  }
+
+  None
+}
+
+// some general-purpose shrinking steps, which individual cases combine as appropriate
+
+// every input with one line removed
+fn remove_lines(input: &str) -> Vec<String> {
+  let lines: Vec<&str> = input.lines().collect();
+  (0..lines.len()).map(|skip| {
+    lines.iter().enumerate().filter(|&(index, _)| index != skip).map(|(_, &line)| line).collect::<Vec<&str>>().join("\n")
+  }).collect()
+}
+
+// every input with one row or one column removed, for inputs which are a rectangular grid of characters
+fn remove_grid_rows_and_columns(input: &str) -> Vec<String> {
+  let rows: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+  let mut result = remove_lines(input);
+  let width = rows.first().map_or(0, |row| row.len());
+  for skip in 0..width {
+    let without_column: Vec<String> = rows.iter().map(|row| {
+      row.iter().enumerate().filter(|&(index, _)| index != skip).map(|(_, &c)| c).collect()
+    }).collect();
+    result.push(without_column.join("\n"));
+  }
+  result
+}
+
+// every input with one of its numbers made smaller - first to 0, then halved, then just reduced by 1
+fn shrink_numbers(input: &str) -> Vec<String> {
+  let mut result = vec![];
+  let chars: Vec<char> = input.chars().collect();
+  let mut index = 0;
+  while index < chars.len() {
+    if chars[index].is_ascii_digit() {
+      let start = index;
+      while index < chars.len() && chars[index].is_ascii_digit() {
+        index += 1;
+      }
+      let number: u64 = chars[start..index].iter().collect::<String>().parse().unwrap();
+      let prefix: String = chars[..start].iter().collect();
+      let suffix: String = chars[index..].iter().collect();
+      let mut smaller = vec![0, number / 2, number.saturating_sub(1)];
+      smaller.dedup();
+      for replacement in smaller {
+        if replacement < number {
+          result.push(format!("{}{}{}", prefix, replacement, suffix));
+        }
+      }
+    } else {
+      index += 1;
+    }
+  }
+  result
+}
+
+// day 5: the backwards search for part 2, against pushing every seed forwards
+
+fn generate_day5(rng: &mut StdRng) -> String {
+  let num_seed_ranges = rng.gen_range(1..=3);
+  let seeds: Vec<String> = (0..num_seed_ranges).flat_map(|_| {
+    let start: u64 = rng.gen_range(0..100);
+    let length: u64 = rng.gen_range(1..20);
+    vec![start.to_string(), length.to_string()]
+  }).collect();
+  let mut lines = vec![format!("seeds: {}", seeds.join(" ")), String::new()];
+
+  let num_maps = rng.gen_range(1..=4);
+  for map_index in 0..num_maps {
+    lines.push(format!("map-{}:", map_index));
+    // the maps in the real input never overlap either on the source or the destination side - the backwards
+    // search relies on this - so build the lines from disjoint intervals taken in a shuffled order
+    let num_lines = rng.gen_range(1..=4);
+    let mut boundaries: Vec<u64> = (0..(2 * num_lines)).map(|_| rng.gen_range(0..150)).collect();
+    boundaries.sort_unstable();
+    boundaries.dedup();
+    let intervals: Vec<(u64, u64)> = boundaries.chunks_exact(2).map(|pair| (pair[0], pair[1] - pair[0])).collect();
+    // the destinations have to be intervals of the same lengths as the sources, so lay them out end-to-end
+    // in a shuffled order, leaving random gaps
+    let mut order: Vec<usize> = (0..intervals.len()).collect();
+    order.shuffle(rng);
+    let mut destination_starts = vec![0; intervals.len()];
+    let mut next_start = rng.gen_range(0..20);
+    for index in order {
+      destination_starts[index] = next_start;
+      next_start += intervals[index].1 + rng.gen_range(0..20);
+    }
+    for (index, &(source_start, length)) in intervals.iter().enumerate() {
+      lines.push(format!("{} {} {}", destination_starts[index], source_start, length));
+    }
+    lines.push(String::new());
+  }
+  lines.pop();
+  lines.join("\n")
+}
+
+// checks that no two lines in the same map overlap, on either the source or the destination side, so that
+// shrinking doesn't wander outside what the real input can look like
+fn is_valid_day5(input: &str) -> bool {
+  for map in input.split("\n\n").skip(1) {
+    if map.starts_with(|c: char| c.is_ascii_digit()) {
+      // lost the title line
+      return false;
+    }
+    let mut sources = vec![];
+    let mut destinations = vec![];
+    for line in map.lines().skip(1) {
+      let nums: Vec<u64> = line.split(' ').map(|s| s.parse().unwrap()).collect();
+      if nums[2] == 0 {
+        return false;
+      }
+      destinations.push((nums[0], nums[0] + nums[2]));
+      sources.push((nums[1], nums[1] + nums[2]));
+    }
+    for intervals in [&mut sources, &mut destinations] {
+      intervals.sort_unstable();
+      if intervals.windows(2).any(|pair| pair[0].1 > pair[1].0) {
+        return false;
+      }
+    }
+  }
+  true
+}
+
+fn shrink_day5(input: &str) -> Vec<String> {
+  // blank lines and map titles have to stay where they are, so only the map lines themselves can be removed.
+  // (Any candidate which removes a title is caught by the validity check below.)
+  let mut result: Vec<String> = remove_lines(input).into_iter().filter(|candidate| {
+    candidate.split("\n\n").count() == input.split("\n\n").count()
+      && candidate.split("\n\n").all(|section| !section.is_empty())
+  }).collect();
+  result.append(&mut shrink_numbers(input));
+  result.retain(|candidate| is_valid_day5(candidate));
+  result
+}
+
+// day 12: counting arrangements with the run-length encoding, against trying every arrangement
+
+fn generate_day12(rng: &mut StdRng) -> String {
+  let num_rows = rng.gen_range(1..=5);
+  (0..num_rows).map(|_| {
+    // start from a definite arrangement so that there's always at least one solution, then hide some of it
+    let length = rng.gen_range(1..=12);
+    let mut springs: Vec<char> = (0..length).map(|_| if rng.gen_bool(0.5) { '#' } else { '.' }).collect();
+    if !springs.contains(&'#') {
+      springs[rng.gen_range(0..length)] = '#';
+    }
+    let groups: Vec<String> = springs.iter().collect::<String>()
+      .split('.')
+      .filter(|group| !group.is_empty())
+      .map(|group| group.len().to_string())
+      .collect();
+    for spring in springs.iter_mut() {
+      if rng.gen_bool(0.6) {
+        *spring = '?';
+      }
+    }
+    format!("{} {}", springs.iter().collect::<String>(), groups.join(","))
+  }).collect::<Vec<String>>().join("\n")
+}
+
+fn shrink_day12(input: &str) -> Vec<String> {
+  let mut result = remove_lines(input);
+  // also try removing single springs, or replacing an unknown one by each of the known types
+  let lines: Vec<&str> = input.lines().collect();
+  for (line_index, line) in lines.iter().enumerate() {
+    let (springs, groups) = line.split_once(' ').unwrap_or((line, ""));
+    let springs: Vec<char> = springs.chars().collect();
+    for spring_index in 0..springs.len() {
+      let mut replacements = vec![None];
+      if springs[spring_index] == '?' {
+        replacements.push(Some('.'));
+        replacements.push(Some('#'));
+      }
+      for replacement in replacements {
+        let mut new_springs = springs.clone();
+        match replacement {
+          None => {
+            new_springs.remove(spring_index);
+          },
+          Some(c) => new_springs[spring_index] = c,
+        }
+        let mut new_lines: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        new_lines[line_index] = format!("{} {}", new_springs.iter().collect::<String>(), groups);
+        result.push(new_lines.join("\n"));
+      }
+    }
+  }
+  result.append(&mut shrink_numbers(input));
+  result
+}
+
+// day 17: Djikstra with the simple hashmap queue, against the binary heap queue
+
+fn generate_day17(rng: &mut StdRng) -> String {
+  // part 2 needs room to move 10 squares in a line
+  let height = rng.gen_range(11..=16);
+  let width = rng.gen_range(11..=16);
+  (0..height).map(|_| {
+    (0..width).map(|_| char::from_digit(rng.gen_range(1..=9), 10).unwrap()).collect::<String>()
+  }).collect::<Vec<String>>().join("\n")
+}
+
+fn shrink_day17(input: &str) -> Vec<String> {
+  let mut result = remove_grid_rows_and_columns(input);
+  // also try flattening single digits down to 1
+  let chars: Vec<char> = input.chars().collect();
+  for (index, &c) in chars.iter().enumerate() {
+    if c.is_ascii_digit() && c != '1' {
+      let mut new_chars = chars.clone();
+      new_chars[index] = '1';
+      result.push(new_chars.into_iter().collect());
+    }
+  }
+  result
+}
+
+pub fn all_cases() -> Vec<DiffCase> {
+  vec![
+    #[cfg(feature = "year2023-day5")]
+    DiffCase {
+      year: 2023,
+      day: 5,
+      part: 2,
+      generate: generate_day5,
+      shrink: shrink_day5,
+      reference: |input| day5::part_2_forwards_from_input(input).to_string(),
+      optimized: |input| day5::part_2_from_input(input).to_string(),
+    },
+    #[cfg(feature = "year2023-day12")]
+    DiffCase {
+      year: 2023,
+      day: 12,
+      part: 1,
+      generate: generate_day12,
+      shrink: shrink_day12,
+      reference: |input| day12::part_1_brute_force_from_input(input).to_string(),
+      optimized: |input| day12::part_1_from_input(input).to_string(),
+    },
+    #[cfg(feature = "year2023-day17")]
+    DiffCase {
+      year: 2023,
+      day: 17,
+      part: 1,
+      generate: generate_day17,
+      shrink: shrink_day17,
+      reference: |input| day17::describe(day17::part_1_from_input(input, day17::PART_1_MIN_DISTANCE, day17::PART_1_MAX_DISTANCE)),
+      optimized: |input| day17::describe(day17::part_1_with_heap_from_input(input, day17::PART_1_MIN_DISTANCE, day17::PART_1_MAX_DISTANCE)),
+    },
+    #[cfg(feature = "year2023-day17")]
+    DiffCase {
+      year: 2023,
+      day: 17,
+      part: 2,
+      generate: generate_day17,
+      shrink: shrink_day17,
+      reference: |input| day17::describe(day17::part_2_from_input(input, day17::PART_2_MIN_DISTANCE, day17::PART_2_MAX_DISTANCE)),
+      optimized: |input| day17::describe(day17::part_2_with_heap_from_input(input, day17::PART_2_MIN_DISTANCE, day17::PART_2_MAX_DISTANCE)),
+    },
+  ]
+}
+
+// entry point for the `difftest` command. Optionally takes a day number to only test that day.
+pub fn run(year: u16, day: Option<u8>, runs: usize, seed: u64, timeout: Duration) -> bool {
+  let mut all_agreed = true;
+  for (case_index, case) in all_cases().iter().enumerate() {
+    if case.year != year || day.is_some_and(|day| day != case.day) {
+      continue;
+    }
+    match run_case(case_index, runs, seed, timeout) {
+      None => println!("{}: all {} inputs agreed", case.name(), runs),
+      Some(Disagreement { case, input, reference, optimized }) => {
+        all_agreed = false;
+        println!("{}: DISAGREEMENT on input:\n{}\nreference gave {}\noptimized gave {}", case, input, reference, optimized);
+      },
+    }
+  }
+  all_agreed
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::solutions::{get_day, Params};
+
+  // The tests run the solvers here rather than in worker processes (this test binary doesn't have the worker
+  // commands), so they stick to inputs that the solvers can handle.
+  const SEED: u64 = 2023;
+
+  #[test]
+  fn generated_inputs_are_accepted_by_the_day() {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    for case in all_cases() {
+      let solver = get_day(case.year, case.day).and_then(|day| day.part(case.part)).unwrap();
+      for _ in 0..10 {
+        let input = (case.generate)(&mut rng);
+        let answer = solver(&input, &Params::default());
+        assert!(answer.parse::<u64>().is_ok(), "{}: gave {} for\n{}", case.name(), answer, input);
+      }
+    }
+  }
+
+  #[test]
+  fn a_short_run_of_each_case_agrees() {
+    for case in all_cases() {
+      let mut rng = StdRng::seed_from_u64(SEED);
+      for _ in 0..20 {
+        let input = (case.generate)(&mut rng);
+        assert_eq!((case.reference)(&input), (case.optimized)(&input), "{} disagrees on\n{}", case.name(), input);
+      }
+    }
+  }
+
+  // For shrinking, a disagreement is made up: a pretend optimized solver for day 12 that's one out whenever there's
+  // more than one arrangement. The input it starts from does have more than one, so shrinking should end up at an
+  // input that still does, but where nothing it could shrink to any further does.
+  #[cfg(feature = "year2023-day12")]
+  #[test]
+  fn shrinking_stops_at_a_local_minimum() {
+    let case = &all_cases()[find_case(2023, 12, 1).unwrap()];
+    let disagrees = |input: &str| {
+      let answer: u64 = std::panic::catch_unwind(|| (case.reference)(input)).ok()?.parse().ok()?;
+      Some((answer, answer + 1)).filter(|_| answer > 1)
+    };
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let input = std::iter::repeat_with(|| (case.generate)(&mut rng)).find(|input| disagrees(input).is_some()).unwrap();
+    let outcomes = disagrees(&input).unwrap();
+    let (shrunk, _) = shrink(case, input.clone(), outcomes, disagrees);
+    assert!(shrunk.len() < input.len(), "{} didn't shrink", input);
+    assert!(disagrees(&shrunk).is_some());
+    for candidate in (case.shrink)(&shrunk) {
+      assert!(disagrees(&candidate).is_none(), "{} could still shrink to {}", shrunk, candidate);
+    }
+  }
+}
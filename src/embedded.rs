@@ -0,0 +1,39 @@
+use crate::solutions::{all_days, Params};
+
+// The inputs baked into the binary when it's built with the `embedded-input` feature (see build.rs), so that it
+// can be handed to someone and run without the input directory next to it. With the feature on these are where
+// the input comes from by default - though `--input` still works as usual.
+
+include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+
+pub const ENABLED: bool = cfg!(feature = "embedded-input");
+
+pub fn get(year: u16, day: u8) -> Result<&'static str, String> {
+  EMBEDDED_INPUTS
+    .iter()
+    .find(|(input_year, input_day, _)| *input_year == year && *input_day == day)
+    .map(|(_, _, input)| *input)
+    .ok_or(format!("no embedded input for {} day {}", year, day))
+}
+
+// the equivalent of `solutions::run` for a self-contained binary - every day's answers, from the embedded inputs
+pub fn run(year: u16) {
+  let days = all_days(year);
+  if days.is_empty() {
+    println!("no solutions for {} yet", year);
+  }
+  for day in days {
+    let input = match get(year, day.number) {
+      Ok(input) => input,
+      Err(message) => {
+        println!("{}", message);
+        continue;
+      },
+    };
+    for part in [1, 2] {
+      if let Some(solver) = day.part(part) {
+        println!("The answer to day {}, part {} is {}", day.number, part, solver(input, &Params::default()));
+      }
+    }
+  }
+}
@@ -0,0 +1,403 @@
+use std::env;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Finds the puzzle input for a day without having to copy it into the `input` directory by hand. Inputs are
+// kept in a cache directory, laid out as <cache>/<year>/<session key>/day<N>.txt - inputs are different for
+// every user, so the session is part of the key. On a cache miss the input is downloaded using the session
+// cookie from the AOC_SESSION environment variable, and once something is in the cache it's never downloaded
+// again.
+//
+// Environment variables:
+//   AOC_SESSION    the value of the "session" cookie from a logged-in browser
+//   AOC_CACHE_DIR  where to keep the inputs (default ./input/cache)
+//   AOC_BASE_URL   where to download from (default https://adventofcode.com)
+
+const DEFAULT_CACHE_DIR: &str = "./input/cache";
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+// the site asks that automated tools identify themselves
+const USER_AGENT: &str = "github.com/robinzigmond/advent-of-code-2023 inputs module";
+
+// never make requests closer together than this, even across separate runs of the program
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(3);
+
+// the bare minimum of an HTTP client that the cache (and answer submission) needs. This is a trait so that
+// the real site can be swapped out for something that doesn't touch the network.
+pub trait HttpClient {
+  // makes a GET request with the given cookie header, returning the status code and body
+  fn get(&self, url: &str, cookie: &str) -> Result<(u16, String), String>;
+
+  // POSTs an already-encoded form, returning the status code and body
+  fn post_form(&self, url: &str, cookie: &str, form: &str) -> Result<(u16, String), String>;
+}
+
+// escapes a value for use inside double quotes in a curl config file
+fn curl_quote(value: &str) -> String {
+  format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// The standard library has no TLS support, so the real site (which is https only) is reached by running curl.
+// The cookie is passed through curl's config on stdin rather than the command line, so that it doesn't show up
+// in the process list.
+pub struct CurlClient;
+
+impl CurlClient {
+  // `config` is any extra curl config, on top of the cookie
+  fn request(&self, url: &str, cookie: &str, config: &str) -> Result<(u16, String), String> {
+    let mut child = Command::new("curl")
+      .args(["--silent", "--show-error", "--config", "-", "--write-out", "\n%{http_code}", "--user-agent", USER_AGENT, url])
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped())
+      .spawn()
+      .map_err(|error| format!("couldn't run curl: {}", error))?;
+    let config = format!("header = {}\n{}", curl_quote(&format!("Cookie: {}", cookie)), config);
+    child.stdin.take().unwrap().write_all(config.as_bytes()).map_err(|error| error.to_string())?;
+    let output = child.wait_with_output().map_err(|error| error.to_string())?;
+    if !output.status.success() {
+      return Err(format!("curl failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    // the status code is on the last line, thanks to --write-out
+    let (body, status) = stdout.rsplit_once('\n').ok_or("curl gave no status code")?;
+    let status = status.parse().map_err(|_| format!("curl gave an invalid status code: {}", status))?;
+    Ok((status, body.to_owned()))
+  }
+}
+
+impl HttpClient for CurlClient {
+  fn get(&self, url: &str, cookie: &str) -> Result<(u16, String), String> {
+    self.request(url, cookie, "")
+  }
+
+  fn post_form(&self, url: &str, cookie: &str, form: &str) -> Result<(u16, String), String> {
+    // curl sends --data as a form POST
+    self.request(url, cookie, &format!("data = {}\n", curl_quote(form)))
+  }
+}
+
+// a plain HTTP/1.1 client using only the standard library - so only for http:// URLs, such as a local mirror
+// or the fake server below
+pub struct PlainHttpClient;
+
+impl PlainHttpClient {
+  fn request(&self, method: &str, url: &str, cookie: &str, form: Option<&str>) -> Result<(u16, String), String> {
+    let rest = url.strip_prefix("http://").ok_or_else(|| format!("not an http:// URL: {}", url))?;
+    let (host, path) = match rest.find('/') {
+      Some(index) => (&rest[..index], &rest[index..]),
+      None => (rest, "/"),
+    };
+    let mut stream = TcpStream::connect(host).map_err(|error| format!("couldn't connect to {}: {}", host, error))?;
+    stream.set_read_timeout(Some(Duration::from_secs(30))).unwrap();
+    let mut request = format!(
+      "{} {} HTTP/1.1\r\nHost: {}\r\nCookie: {}\r\nUser-Agent: {}\r\nConnection: close\r\n",
+      method, path, host, cookie, USER_AGENT
+    );
+    match form {
+      Some(form) => request.push_str(&format!(
+        "Content-Type: application/x-www-form-urlencoded\r\nContent-Length: {}\r\n\r\n{}",
+        form.len(),
+        form
+      )),
+      None => request.push_str("\r\n"),
+    }
+    stream.write_all(request.as_bytes()).map_err(|error| error.to_string())?;
+
+    // as we asked for the connection to be closed, the body is everything up to the end of the stream
+    let mut response = String::new();
+    stream.read_to_string(&mut response).map_err(|error| error.to_string())?;
+    let (head, body) = response.split_once("\r\n\r\n").ok_or("incomplete response")?;
+    let status = head
+      .split(' ')
+      .nth(1)
+      .and_then(|status| status.parse().ok())
+      .ok_or_else(|| format!("malformed status line: {}", head.lines().next().unwrap_or("")))?;
+    Ok((status, body.to_owned()))
+  }
+}
+
+impl HttpClient for PlainHttpClient {
+  fn get(&self, url: &str, cookie: &str) -> Result<(u16, String), String> {
+    self.request("GET", url, cookie, None)
+  }
+
+  fn post_form(&self, url: &str, cookie: &str, form: &str) -> Result<(u16, String), String> {
+    self.request("POST", url, cookie, Some(form))
+  }
+}
+
+pub fn client_for(base_url: &str) -> Box<dyn HttpClient> {
+  if base_url.starts_with("http://") {
+    Box::new(PlainHttpClient)
+  } else {
+    Box::new(CurlClient)
+  }
+}
+
+// A short, stable name for a session to use in the cache path, so that the cookie itself isn't written to
+// disk. This is 64-bit FNV-1a (https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function) -
+// std's DefaultHasher isn't guaranteed to give the same results in different versions of Rust, which would
+// make the cache quietly miss after upgrading.
+fn session_key(session: &str) -> String {
+  let mut hash: u64 = 0xcbf29ce484222325;
+  for byte in session.bytes() {
+    hash ^= byte as u64;
+    hash = hash.wrapping_mul(0x100000001b3);
+  }
+  format!("{:016x}", hash)
+}
+
+fn now_micros() -> u128 {
+  SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_micros()
+}
+
+pub struct InputCache {
+  pub dir: PathBuf,
+  pub base_url: String,
+  pub session: Option<String>,
+  pub min_interval: Duration,
+  pub client: Box<dyn HttpClient>,
+}
+
+impl InputCache {
+  pub fn from_env() -> InputCache {
+    let base_url = env::var("AOC_BASE_URL").unwrap_or(String::from(DEFAULT_BASE_URL));
+    InputCache {
+      dir: PathBuf::from(env::var("AOC_CACHE_DIR").unwrap_or(String::from(DEFAULT_CACHE_DIR))),
+      client: client_for(&base_url),
+      base_url,
+      session: env::var("AOC_SESSION").ok().map(|session| session.trim().to_owned()).filter(|session| !session.is_empty()),
+      min_interval: MIN_REQUEST_INTERVAL,
+    }
+  }
+
+  pub fn session(&self) -> Result<&str, String> {
+    self.session.as_deref().ok_or(String::from("no session cookie - set the AOC_SESSION environment variable"))
+  }
+
+  // where everything to do with one session's puzzles for the year is kept
+  pub fn session_dir(&self, year: u16) -> Result<PathBuf, String> {
+    let session = self.session()?;
+    Ok(self.dir.join(year.to_string()).join(session_key(session)))
+  }
+
+  pub fn path(&self, year: u16, day: u8) -> Result<PathBuf, String> {
+    Ok(self.session_dir(year)?.join(format!("day{}.txt", day)))
+  }
+
+  // the time of the last request is kept in the cache directory, so that the rate limit still holds when
+  // lots of separate commands are run one after another
+  pub fn wait_for_rate_limit(&self) -> Result<(), String> {
+    let marker = self.dir.join("last-request");
+    if let Ok(contents) = fs::read_to_string(&marker) {
+      if let Ok(last) = contents.trim().parse::<u128>() {
+        let elapsed = now_micros().saturating_sub(last);
+        let interval = self.min_interval.as_micros();
+        if elapsed < interval {
+          thread::sleep(Duration::from_micros((interval - elapsed) as u64));
+        }
+      }
+    }
+    fs::create_dir_all(&self.dir).map_err(|error| error.to_string())?;
+    fs::write(&marker, now_micros().to_string()).map_err(|error| error.to_string())
+  }
+
+  fn download(&self, year: u16, day: u8) -> Result<String, String> {
+    let session = self.session()?;
+    self.wait_for_rate_limit()?;
+    let url = format!("{}/{}/day/{}/input", self.base_url.trim_end_matches('/'), year, day);
+    let (status, body) = self.client.get(&url, &format!("session={}", session))?;
+    match status {
+      200 => Ok(body),
+      400 | 401 | 500 => Err(format!("the server rejected the session cookie ({} from {})", status, url)),
+      404 => Err(format!("no input for {} day {} yet ({} from {})", year, day, status, url)),
+      429 => Err(format!("rate limited by the server ({} from {}) - try again later", status, url)),
+      _ => Err(format!("unexpected response {} from {}", status, url)),
+    }
+  }
+
+  // the input for a day, from the cache if possible, otherwise downloaded and added to the cache
+  pub fn get(&self, year: u16, day: u8) -> Result<String, String> {
+    let path = self.path(year, day)?;
+    if let Ok(input) = fs::read_to_string(&path) {
+      return Ok(input);
+    }
+    let input = self.download(year, day)?;
+    write_atomically(&path, &input)?;
+    Ok(input)
+  }
+}
+
+// writes to a temporary file first, so that a run that's killed halfway through can't leave a truncated input
+// in the cache (which would then never be downloaded again)
+fn write_atomically(path: &Path, contents: &str) -> Result<(), String> {
+  fs::create_dir_all(path.parent().unwrap()).map_err(|error| error.to_string())?;
+  let temporary = path.with_extension(format!("tmp{}", std::process::id()));
+  fs::write(&temporary, contents).map_err(|error| error.to_string())?;
+  fs::rename(&temporary, path).map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::net::TcpListener;
+  use std::sync::{Arc, Mutex};
+  use std::time::Instant;
+
+  // An in-process stand-in for the real site, for testing the cache without any network access. It serves
+  // "input for <year> day <day>, session <session>" to the sessions it knows about, and records every request.
+  struct FakeServer {
+    address: String,
+    requests: Arc<Mutex<Vec<(String, Instant)>>>,
+  }
+
+  impl FakeServer {
+    fn start(valid_sessions: &[&str]) -> FakeServer {
+      let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+      let address = listener.local_addr().unwrap().to_string();
+      let requests = Arc::new(Mutex::new(vec![]));
+      let recorded = requests.clone();
+      let valid_sessions: Vec<String> = valid_sessions.iter().map(|session| format!("session={}", session)).collect();
+      thread::spawn(move || {
+        for stream in listener.incoming() {
+          let mut stream = stream.unwrap();
+          let mut buffer = [0; 4096];
+          let length = stream.read(&mut buffer).unwrap();
+          let request = String::from_utf8_lossy(&buffer[..length]).into_owned();
+          let path = request.split(' ').nth(1).unwrap_or("").to_owned();
+          let cookie = request.lines().find_map(|line| line.strip_prefix("Cookie: ")).unwrap_or("").to_owned();
+          recorded.lock().unwrap().push((path.clone(), Instant::now()));
+
+          let parts: Vec<&str> = path.split('/').collect();
+          let (status, body) = match parts.as_slice() {
+            ["", year, "day", day, "input"] if day.parse::<u8>().is_ok_and(|day| day <= 25) => {
+              if valid_sessions.contains(&cookie) {
+                (200, format!("input for {} day {}, {}\n", year, day, cookie))
+              } else {
+                (400, String::from("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"))
+              }
+            },
+            _ => (404, String::from("404 Not Found\n")),
+          };
+          let response = format!("HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body);
+          stream.write_all(response.as_bytes()).unwrap();
+        }
+      });
+      FakeServer { address, requests }
+    }
+
+    fn request_count(&self) -> usize {
+      self.requests.lock().unwrap().len()
+    }
+  }
+
+  // an empty cache directory for one test, so that the tests can run at the same time without sharing a cache
+  fn cache_dir(test: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-inputs-test-{}-{}", test, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+  }
+
+  fn cache_for(server: &FakeServer, dir: &Path, session: &str) -> InputCache {
+    InputCache {
+      dir: dir.to_owned(),
+      base_url: format!("http://{}", server.address),
+      session: Some(session.to_owned()),
+      min_interval: Duration::from_millis(300),
+      client: Box::new(PlainHttpClient),
+    }
+  }
+
+  #[test]
+  fn a_cache_miss_downloads_and_saves_the_input() {
+    let server = FakeServer::start(&["alice"]);
+    let dir = cache_dir("miss");
+    let alice = cache_for(&server, &dir, "alice");
+    assert_eq!(alice.get(2023, 1), Ok(String::from("input for 2023 day 1, session=alice\n")));
+    assert_eq!(server.request_count(), 1);
+    assert!(alice.path(2023, 1).unwrap().exists());
+    let _ = fs::remove_dir_all(&dir);
+  }
+
+  #[test]
+  fn a_cache_hit_makes_no_request() {
+    let server = FakeServer::start(&["alice"]);
+    let dir = cache_dir("hit");
+    let alice = cache_for(&server, &dir, "alice");
+    let first = alice.get(2023, 1);
+    assert_eq!(alice.get(2023, 1), first);
+    assert_eq!(server.request_count(), 1);
+    let _ = fs::remove_dir_all(&dir);
+  }
+
+  #[test]
+  fn each_session_has_its_own_input() {
+    let server = FakeServer::start(&["alice", "bob"]);
+    let dir = cache_dir("sessions");
+    let (alice, bob) = (cache_for(&server, &dir, "alice"), cache_for(&server, &dir, "bob"));
+    alice.get(2023, 1).unwrap();
+    assert_eq!(bob.get(2023, 1), Ok(String::from("input for 2023 day 1, session=bob\n")));
+    assert_ne!(alice.path(2023, 1), bob.path(2023, 1));
+    assert_eq!(server.request_count(), 2);
+    let _ = fs::remove_dir_all(&dir);
+  }
+
+  #[test]
+  fn the_year_and_day_are_part_of_the_key() {
+    let server = FakeServer::start(&["alice"]);
+    let dir = cache_dir("key");
+    let alice = cache_for(&server, &dir, "alice");
+    alice.get(2023, 1).unwrap();
+    assert_eq!(alice.get(2022, 1), Ok(String::from("input for 2022 day 1, session=alice\n")));
+    assert_eq!(alice.get(2023, 2), Ok(String::from("input for 2023 day 2, session=alice\n")));
+    assert_eq!(server.request_count(), 3);
+    let _ = fs::remove_dir_all(&dir);
+  }
+
+  #[test]
+  fn requests_are_spaced_out() {
+    let server = FakeServer::start(&["alice"]);
+    let dir = cache_dir("spacing");
+    let alice = cache_for(&server, &dir, "alice");
+    for day in 1..=3 {
+      alice.get(2023, day).unwrap();
+    }
+    let requests = server.requests.lock().unwrap();
+    assert_eq!(requests.len(), 3);
+    // the server records the time it reads each request, so allow a little leeway for scheduling
+    for pair in requests.windows(2) {
+      assert!(pair[1].1.duration_since(pair[0].1) >= Duration::from_millis(250));
+    }
+    drop(requests);
+    let _ = fs::remove_dir_all(&dir);
+  }
+
+  #[test]
+  fn failed_downloads_are_errors_and_not_cached() {
+    let server = FakeServer::start(&["alice"]);
+    let dir = cache_dir("failures");
+    let mallory = cache_for(&server, &dir, "mallory");
+    assert!(mallory.get(2023, 1).unwrap_err().contains("session cookie"));
+    assert!(!mallory.path(2023, 1).unwrap().exists());
+    let alice = cache_for(&server, &dir, "alice");
+    assert!(alice.get(2023, 26).is_err());
+    assert!(!alice.path(2023, 26).unwrap().exists());
+    let _ = fs::remove_dir_all(&dir);
+  }
+
+  #[test]
+  fn a_missing_session_is_an_error() {
+    let server = FakeServer::start(&["alice"]);
+    let dir = cache_dir("no-session");
+    let no_session = InputCache { session: None, ..cache_for(&server, &dir, "") };
+    assert!(no_session.get(2023, 1).unwrap_err().contains("AOC_SESSION"));
+    assert_eq!(server.request_count(), 0);
+    let _ = fs::remove_dir_all(&dir);
+  }
+}
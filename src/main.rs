@@ -1,70 +1,451 @@
+// A build with only some of the days in it (see the features in Cargo.toml) leaves bits of the shared code
+// unused, which is fine.
+#![cfg_attr(not(feature = "year2023"), allow(dead_code, unused_imports, unused_macros))]
+
+mod batch;
+mod bench;
+#[cfg(feature = "difftest")]
+mod difftest;
+mod embedded;
+mod inputs;
+mod minimize;
+mod profile;
+mod report;
+mod scaffold;
+mod serve;
 mod solutions;
+mod submit;
+mod trace;
+mod worker;
 
 use crate::solutions::*;
+#[cfg(feature = "year2023-day1")]
+use crate::solutions::year2023::day1;
+#[cfg(feature = "year2023-day2")]
+use crate::solutions::year2023::day2;
+#[cfg(feature = "year2023-day3")]
+use crate::solutions::year2023::day3;
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::path::Path;
+use std::process;
 use std::thread;
+use std::time::Duration;
 
 // need a larger stack for day 16 puzzle using recursive approach!
 // Found how to do it from https://www.reddit.com/r/rust/comments/872fc4/how_to_increase_the_stack_size/
 const STACK_SIZE: usize = 4 * 1024 * 1024;
 
-fn run() {
-  println!("The answer to day 1, part 1 is {}", day1::part_1());
-  println!("The answer to day 1, part 2 is {}", day1::part_2());
-  println!("The answer to day 2, part 1 is {}", day2::part_1());
-  println!("The answer to day 2, part 2 is {}", day2::part_2());
-  println!("The answer to day 3, part 1 is {}", day3::part_1());
-  println!("The answer to day 3, part 2 is {}", day3::part_2());
-  println!("The answer to day 4, part 1 is {}", day4::part_1());
-  println!("The answer to day 4, part 2 is {}", day4::part_2());
-  println!("The answer to day 5, part 1 is {}", day5::part_1());
-  println!("The answer to day 5, part 2 is {}", day5::part_2());
-  println!("The answer to day 6, part 1 is {}", day6::part_1());
-  println!("The answer to day 6, part 2 is {}", day6::part_2());
-  println!("The answer to day 7, part 1 is {}", day7::part_1());
-  println!("The answer to day 7, part 2 is {}", day7::part_2());
-  println!("The answer to day 8, part 1 is {}", day8::part_1());
-  // day8::get_info();
-  println!("The answer to day 8, part 2 is {}", day8::part_2());
-  println!("The answer to day 9, part 1 is {}", day9::part_1());
-  println!("The answer to day 9, part 2 is {}", day9::part_2());
-  println!("The answer to day 10, part 1 is {}", day10::part_1());
-  println!("The answer to day 10, part 2 is {}", day10::part_2());
-  println!("The answer to day 11, part 1 is {}", day11::part_1());
-  println!("The answer to day 11, part 2 is {}", day11::part_2());
-  println!("The answer to day 12, part 1 is {}", day12::part_1());
-  println!("The answer to day 12, part 2 is {}", day12::part_2());
-  println!("The answer to day 13, part 1 is {}", day13::part_1());
-  println!("The answer to day 13, part 2 is {}", day13::part_2());
-  println!("The answer to day 14, part 1 is {}", day14::part_1());
-  println!("The answer to day 14, part 2 is {}", day14::part_2());
-  println!("The answer to day 15, part 1 is {}", day15::part_1());
-  println!("The answer to day 15, part 2 is {}", day15::part_2());
-  println!("The answer to day 16, part 1 is {}", day16::part_1());
-  println!("The answer to day 16, part 2 is {}", day16::part_2());
-  println!("The answer to day 17, part 1 is {}", day17::part_1());
-  println!("The answer to day 17, part 2 is {}", day17::part_2());
-  println!("The answer to day 18, part 1 is {}", day18::part_1());
-  println!("The answer to day 18, part 2 is {}", day18::part_2());
-  println!("The answer to day 19, part 1 is {}", day19::part_1());
-  println!("The answer to day 19, part 2 is {}", day19::part_2());
-  println!("The answer to day 20, part 1 is {}", day20::part_1());
-  println!("The answer to day 20, part 2 is {}", day20::part_2());
-  println!("The answer to day 21, part 1 is {}", day21::part_1());
-  println!("The answer to day 21, part 2 is {}", day21::part_2());
-  println!("The answer to day 22, part 1 is {}", day22::part_1());
-  println!("The answer to day 22, part 2 is {}", day22::part_2());
-  println!("The answer to day 23, part 1 is {}", day23::part_1());
-  println!("The answer to day 23, part 2 is {}", day23::part_2());
-  println!("The answer to day 24, part 1 is {}", day24::part_1());
-  println!("The answer to day 24, part 2 is {}", day24::part_2());
-  println!("The answer to day 25, part 1 is {}", day25::part_1());
+// the year to use when --year isn't given
+const DEFAULT_YEAR: u16 = 2023;
+
+// the days of the year that are in this build, as eg. "1-5, 8, 10-25"
+fn describe_days(year: u16) -> String {
+  // the registry should already have them in order, but the ranges below rely on it
+  let mut numbers: Vec<u8> = all_days(year).iter().map(|day| day.number).collect();
+  numbers.sort_unstable();
+  if numbers.is_empty() {
+    return String::from("none");
+  }
+  let mut ranges: Vec<(u8, u8)> = vec![];
+  for number in numbers {
+    match ranges.last_mut() {
+      Some((_, end)) if *end + 1 == number => *end = number,
+      _ => ranges.push((number, number)),
+    }
+  }
+  let ranges: Vec<String> = ranges
+    .iter()
+    .map(|&(start, end)| if start == end { start.to_string() } else { format!("{}-{}", start, end) })
+    .collect();
+  ranges.join(", ")
+}
+
+// looks up the value given after a `--name` option on the command line, if there is one
+fn get_option(args: &[String], name: &str) -> Option<String> {
+  let flag = format!("--{}", name);
+  args.iter().position(|arg| arg == &flag).and_then(|index| args.get(index + 1)).cloned()
+}
+
+fn parse_option<T: std::str::FromStr>(args: &[String], name: &str, default: T) -> T {
+  match get_option(args, name) {
+    None => default,
+    Some(value) => value.parse().unwrap_or_else(|_| {
+      eprintln!("invalid value for --{}: {}", name, value);
+      process::exit(2);
+    }),
+  }
+}
+
+// the day's parameters given on the command line, exiting just like `parse_option` if any of them is invalid
+fn get_params(args: &[String], day: &Day) -> Params {
+  Params::from_args(args, day).unwrap_or_else(|message| {
+    eprintln!("{}", message);
+    process::exit(2);
+  })
+}
+
+fn get_day_option(args: &[String]) -> Day {
+  let year = parse_option(args, "year", DEFAULT_YEAR);
+  let number = parse_option(args, "day", 0);
+  get_day(year, number).unwrap_or_else(|| {
+    // each day has its own feature, so it might exist but have been left out of this build
+    eprintln!("no solution for {} day {} in this build (compiled in: {})", year, number, describe_days(year));
+    process::exit(2);
+  })
+}
+
+// The input given by --input, or failing that the usual one in the input directory if it's there, or failing
+// that the one from the input cache (downloading it if need be). A binary built with the inputs embedded in it
+// only ever uses those, apart from --input.
+fn find_input(args: &[String], day: &Day) -> Result<String, String> {
+  let default_path = input_path(day.year, day.number);
+  match get_option(args, "input") {
+    Some(path) => fs::read_to_string(&path).map_err(|error| format!("couldn't read {}: {}", path, error)),
+    None if embedded::ENABLED => embedded::get(day.year, day.number).map(|input| input.to_owned()),
+    None if Path::new(&default_path).exists() => fs::read_to_string(&default_path).map_err(|error| error.to_string()),
+    None => inputs::InputCache::from_env().get(day.year, day.number),
+  }
+}
+
+fn read_input(args: &[String], day: &Day) -> String {
+  find_input(args, day).unwrap_or_else(|message| {
+    eprintln!("{}", message);
+    process::exit(1);
+  })
+}
+
+// every answer for the year, from wherever the inputs are
+fn run_year(year: u16) {
+  if embedded::ENABLED {
+    embedded::run(year);
+  } else {
+    solutions::run(year);
+  }
+}
+
+fn run_command(args: Vec<String>) {
+  match args.first().map(|arg| arg.as_str()) {
+    None => run_year(DEFAULT_YEAR),
+    Some("run") => run_year(parse_option(&args, "year", DEFAULT_YEAR)),
+    #[cfg(feature = "difftest")]
+    Some("difftest") => {
+      let year = parse_option(&args, "year", DEFAULT_YEAR);
+      let day = get_option(&args, "day").map(|_| parse_option(&args, "day", 0));
+      let runs = parse_option(&args, "runs", 200);
+      let seed = parse_option(&args, "seed", 2023);
+      let timeout = Duration::from_millis(parse_option(&args, "timeout-ms", 2000));
+      if !difftest::run(year, day, runs, seed, timeout) {
+        process::exit(1);
+      }
+    },
+    // not meant to be run by hand - this is how the difftest command runs each solver in its own process
+    #[cfg(feature = "difftest")]
+    Some("difftest-worker") => {
+      let case_index = parse_option(&args, "case", 0);
+      let solver = get_option(&args, "solver").unwrap_or_default();
+      difftest::run_worker(case_index, &solver);
+    },
+    Some("minimize") => {
+      let day = get_day_option(&args);
+      let part = parse_option(&args, "part", 1);
+      let input_path = get_option(&args, "input").unwrap_or(input_path(day.year, day.number));
+      let output_path = get_option(&args, "output").unwrap_or(format!("{}.min", input_path));
+      let params = get_params(&args, &day);
+      let predicate = parse_option(&args, "predicate", minimize::Predicate::Panic);
+      let timeout = Duration::from_millis(parse_option(&args, "timeout-ms", 10000));
+      // found the same way as for `solve`, so a missing file is an error message rather than a panic
+      let input = read_input(&args, &day);
+      match minimize::minimize(&day, part, &input, params, predicate, timeout) {
+        Ok(minimized) => {
+          if let Err(error) = fs::write(&output_path, &minimized) {
+            eprintln!("couldn't write {}: {}", output_path, error);
+            process::exit(1);
+          }
+          println!("minimized from {} to {} lines - written to {}", input.lines().count(), minimized.lines().count(), output_path);
+        },
+        Err(message) => {
+          eprintln!("{}", message);
+          process::exit(1);
+        },
+      }
+    },
+    // runs one or both parts of a single day, optionally on a different input and with different values for
+    // any of the constants in the puzzle (see `Params`)
+    Some("solve") => {
+      let day = get_day_option(&args);
+      let params = get_params(&args, &day);
+      let parts = match get_option(&args, "part") {
+        None => vec![1, 2],
+        Some(_) => vec![parse_option(&args, "part", 1)],
+      };
+      let input = read_input(&args, &day);
+      // `--explain` narrates what the solution did, at the level of detail given (summary if none is)
+      if let Some(index) = args.iter().position(|arg| arg == "--explain") {
+        let level = match args.get(index + 1).filter(|arg| !arg.starts_with("--")) {
+          Some(level) => level.parse().unwrap_or_else(|message: String| {
+            eprintln!("{}", message);
+            process::exit(2);
+          }),
+          None => trace::Level::Summary,
+        };
+        trace::set_level(level);
+      }
+      for part in parts {
+        match day.part(part) {
+          Some(solver) => {
+            let answer = solver(&input, &params);
+            for warning in trace::take_warnings() {
+              eprintln!("warning: {}", warning);
+            }
+            let events = trace::take_events();
+            if !events.is_empty() {
+              println!("{}", trace::narrative(&events));
+            }
+            println!("The answer to day {}, part {} is {}", day.number, part, answer);
+          },
+          None if part == 1 || part == 2 => (),
+          None => {
+            eprintln!("day {} has no part {}", day.number, part);
+            process::exit(2);
+          },
+        }
+      }
+    },
+    Some("inputs") => match args.get(1).map(|arg| arg.as_str()) {
+      // downloads a day's input into the cache if it isn't there already
+      Some("fetch") => {
+        let cache = inputs::InputCache::from_env();
+        let year = parse_option(&args, "year", DEFAULT_YEAR);
+        let day = parse_option(&args, "day", 0);
+        match cache.get(year, day).and_then(|_| cache.path(year, day)) {
+          Ok(path) => println!("{}", path.display()),
+          Err(message) => {
+            eprintln!("{}", message);
+            process::exit(1);
+          },
+        }
+      },
+      _ => {
+        eprintln!("usage: advent_of_code inputs fetch --day N [--year N]");
+        process::exit(2);
+      },
+    },
+    // submits an answer - either the one given, or otherwise whatever the solution comes up with
+    Some("submit") => {
+      let day = get_day_option(&args);
+      let part = parse_option(&args, "part", 1);
+      let answer = match get_option(&args, "answer") {
+        Some(answer) => answer,
+        None => {
+          let solver = day.part(part).unwrap_or_else(|| {
+            eprintln!("day {} has no part {}", day.number, part);
+            process::exit(2);
+          });
+          let answer = solver(&read_input(&args, &day), &get_params(&args, &day));
+          println!("The answer to day {}, part {} is {}", day.number, part, answer);
+          answer
+        },
+      };
+      match submit::submit(&inputs::InputCache::from_env(), day.year, day.number, part, &answer) {
+        Ok(verdict) => println!("{}", verdict.describe()),
+        Err(message) => {
+          eprintln!("{}", message);
+          process::exit(1);
+        },
+      }
+    },
+    // runs one part of one day over every input in a directory
+    Some("batch") => {
+      let day = get_day_option(&args);
+      let part = parse_option(&args, "part", 1);
+      let dir = get_option(&args, "dir").unwrap_or_else(|| {
+        eprintln!("the batch command needs a --dir of inputs");
+        process::exit(2);
+      });
+      let params = get_params(&args, &day);
+      let default_jobs = thread::available_parallelism().map_or(1, |jobs| jobs.get());
+      let jobs = parse_option(&args, "jobs", default_jobs);
+      let timeout = Duration::from_millis(parse_option(&args, "timeout-ms", 60000));
+      match batch::run(&day, part, &params, Path::new(&dir), jobs, timeout) {
+        Ok(true) => (),
+        Ok(false) => process::exit(1),
+        Err(message) => {
+          eprintln!("{}", message);
+          process::exit(2);
+        },
+      }
+    },
+    // each line of day 1's calibration document with the digits and words found in it and the value they give
+    #[cfg(feature = "year2023-day1")]
+    Some("calibration") => {
+      let rule = parse_option(&args, "calibration-rule", day1::Rule::FirstAndLast);
+      let words = day1::NumberWords::load(&get_option(&args, "number-words").unwrap_or(String::from(day1::NUMBER_WORDS)));
+      let words = words.unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        process::exit(2);
+      });
+      let input = read_input(&args, &get_day(2023, 1).unwrap());
+      println!("{}", day1::calibration_report(&input, &words, rule));
+    },
+    // questions about the bags that day 2's games could have been played with
+    #[cfg(feature = "year2023-day2")]
+    Some("bags") => {
+      let query = match args.get(1).map(|arg| arg.as_str()) {
+        Some("feasible") => day2::Query::Feasible(parse_option(&args, "bag", day2::BAG.parse().unwrap())),
+        Some("minimal") => {
+          let ids = get_option(&args, "games").unwrap_or_default();
+          day2::Query::Minimal(ids.split(',').map(|id| id.trim().parse().unwrap_or_else(|_| {
+            eprintln!("invalid game id: {}", id);
+            process::exit(2);
+          })).collect())
+        },
+        Some("best") => day2::Query::Best(parse_option(&args, "budget", 0)),
+        Some("infer") => {
+          let game = get_option(&args, "game").map(|_| parse_option(&args, "game", 0));
+          day2::Query::Infer(game, parse_option(&args, "max-cubes", day2::MAX_CUBES))
+        },
+        Some("simulate") => {
+          let max_cubes = parse_option(&args, "max-cubes", day2::MAX_CUBES);
+          day2::Query::Simulate(parse_option(&args, "seed", 2023), parse_option(&args, "runs", 200), max_cubes)
+        },
+        _ => {
+          eprintln!("usage: advent_of_code bags feasible [--bag SPEC] | bags minimal --games ID,ID... | bags best --budget N");
+          eprintln!("                      | bags infer [--game ID] [--max-cubes N] | bags simulate [--seed N] [--runs N] [--max-cubes N]");
+          process::exit(2);
+        },
+      };
+      let input = match query {
+        day2::Query::Simulate(..) => String::new(),
+        _ => read_input(&args, &get_day(2023, 2).unwrap()),
+      };
+      match day2::query(&input, &query) {
+        Ok(answer) => println!("{}", answer),
+        Err(message) => {
+          eprintln!("{}", message);
+          process::exit(1);
+        },
+      }
+    },
+    // day 3's schematic with what each number and symbol was taken to be marked on it
+    #[cfg(feature = "year2023-day3")]
+    Some("schematic") => {
+      let default_style = if io::stdout().is_terminal() { day3::Annotation::Ansi } else { day3::Annotation::Brackets };
+      let style = parse_option(&args, "style", default_style);
+      let rules = parse_option(&args, "symbol-rules", day3::SYMBOL_RULES.parse().unwrap());
+      let input = read_input(&args, &get_day(2023, 3).unwrap());
+      println!("{}", day3::annotate(&input, &rules, style));
+    },
+    // times one or both parts of a day, along with the allocations made and the solution's own counters
+    Some("bench") => {
+      let day = get_day_option(&args);
+      let part = get_option(&args, "part").map(|_| parse_option(&args, "part", 1));
+      let runs = parse_option(&args, "runs", 5);
+      let params = get_params(&args, &day);
+      let input = read_input(&args, &day);
+      if let Err(message) = bench::run(&day, part, &input, &params, runs) {
+        eprintln!("{}", message);
+        process::exit(2);
+      }
+    },
+    // solves every day of the year and writes up the results, with pictures, as a page to share
+    Some("report") => {
+      let year = parse_option(&args, "year", DEFAULT_YEAR);
+      let format = parse_option(&args, "format", report::Format::Html);
+      let output = get_option(&args, "output").unwrap_or(format!("report.{}", format.extension()));
+      let timeout = Duration::from_millis(parse_option(&args, "timeout-ms", 120000));
+      // every day's usual input - an --input wouldn't make sense for all of them
+      let result = report::build(year, format, timeout, &|day| find_input(&[], day))
+        .and_then(|report| fs::write(&output, report).map_err(|error| format!("couldn't write {}: {}", output, error)));
+      match result {
+        Ok(()) => println!("wrote {}", output),
+        Err(message) => {
+          eprintln!("{}", message);
+          process::exit(1);
+        },
+      }
+    },
+    // sets up the module for a new day's solution
+    Some("scaffold") => {
+      let year = parse_option(&args, "year", DEFAULT_YEAR);
+      let day = parse_option(&args, "day", 0);
+      let shape = get_option(&args, "shape").unwrap_or(String::from("lines"));
+      match scaffold::scaffold(year, day, &shape) {
+        Ok(changed) => {
+          for path in changed {
+            println!("wrote {}", path);
+          }
+        },
+        Err(message) => {
+          eprintln!("{}", message);
+          process::exit(1);
+        },
+      }
+    },
+    Some("serve") => {
+      serve::serve(serve::Config {
+        year: parse_option(&args, "year", DEFAULT_YEAR),
+        port: parse_option(&args, "port", 8080),
+        max_body_bytes: parse_option(&args, "max-body-bytes", 1024 * 1024),
+        timeout: Duration::from_millis(parse_option(&args, "timeout-ms", 30000)),
+      });
+    },
+    // again not for running by hand: runs one part of one day on the input given on stdin
+    Some("solve-worker") => {
+      let day = get_day_option(&args);
+      let solver = day.part(parse_option(&args, "part", 1)).unwrap();
+      let params = get_params(&args, &day);
+      let mut input = String::new();
+      io::stdin().read_to_string(&mut input).unwrap();
+      print!("{}", solver(&input, &params));
+    },
+    Some(other) => {
+      eprintln!("unknown command: {}", other);
+      eprintln!("usage: advent_of_code [run] [--year N]");
+      eprintln!("       advent_of_code solve --day N [--year N] [--part N] [--input PATH] [--explain [summary|detail|everything]] [--PARAMETER VALUE...]");
+      if cfg!(feature = "difftest") {
+        eprintln!("       advent_of_code difftest [--year N] [--day N] [--runs N] [--seed N] [--timeout-ms N]");
+      }
+      eprintln!("       advent_of_code minimize --day N [--year N] [--part N] [--input PATH] [--output PATH]");
+      eprintln!("                      [--predicate panic|mismatch|timeout] [--timeout-ms N] [--PARAMETER VALUE...]");
+      eprintln!("       advent_of_code batch --day N --dir PATH [--year N] [--part N] [--jobs N] [--timeout-ms N]");
+      eprintln!("                      [--PARAMETER VALUE...]");
+      eprintln!("       advent_of_code bench --day N [--year N] [--part N] [--runs N] [--input PATH] [--PARAMETER VALUE...]");
+      if cfg!(feature = "year2023-day1") {
+        eprintln!("       advent_of_code calibration [--number-words NAME|PATH] [--calibration-rule RULE] [--input PATH]");
+      }
+      if cfg!(feature = "year2023-day2") {
+        eprintln!("       advent_of_code bags feasible [--bag SPEC] | bags minimal --games ID,ID... | bags best --budget N");
+        eprintln!("                      | bags infer [--game ID] [--max-cubes N] | bags simulate [--seed N] [--runs N]");
+        eprintln!("                      [--max-cubes N] [--input PATH]");
+      }
+      if cfg!(feature = "year2023-day3") {
+        eprintln!("       advent_of_code schematic [--style ansi|brackets|list] [--symbol-rules RULES] [--input PATH]");
+      }
+      eprintln!("       advent_of_code report [--year N] [--format html|markdown] [--output PATH] [--timeout-ms N]");
+      eprintln!("       advent_of_code inputs fetch --day N [--year N]");
+      eprintln!("       advent_of_code submit --day N [--year N] [--part N] [--answer X]");
+      eprintln!("       advent_of_code serve [--year N] [--port N] [--max-body-bytes N] [--timeout-ms N]");
+      eprintln!("       advent_of_code scaffold --day N [--year N] [--shape lines|grid|sections|comma-separated]");
+      eprintln!("the year is {} unless given", DEFAULT_YEAR);
+      eprintln!("days compiled in for {}: {}", DEFAULT_YEAR, describe_days(DEFAULT_YEAR));
+      for day in all_days(DEFAULT_YEAR).iter().filter(|day| !day.params.is_empty()) {
+        let params: Vec<String> = day.params.iter().map(|param| format!("--{}", param.name)).collect();
+        eprintln!("  parameters for day {}: {}", day.number, params.join(", "));
+      }
+      process::exit(2);
+    },
+  }
 }
 
 fn main() {
+  let args: Vec<String> = env::args().skip(1).collect();
+
   // Spawn thread with explicit stack size
   let child = thread::Builder::new()
       .stack_size(STACK_SIZE)
-      .spawn(run)
+      .spawn(move || run_command(args))
       .unwrap();
 
   // Wait for thread to join
@@ -0,0 +1,211 @@
+#[cfg(feature = "difftest")]
+use crate::difftest;
+use crate::solutions::{Day, InputShape, Params};
+use crate::worker::{self, Outcome};
+use std::str::FromStr;
+use std::time::Duration;
+
+// Cuts a failing puzzle input down to as small an input as possible that still fails in the same way, using
+// delta debugging (https://en.wikipedia.org/wiki/Delta_debugging). Which pieces get removed depends on the
+// shape of the day's input: whole sections, lines, grid rows and columns, or individual tokens within a line.
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Predicate {
+  // the solver panics - at the same place in the code as it did on the original input
+  Panic,
+  // the solver disagrees with the reference solver from the differential tests
+  Mismatch,
+  // the solver doesn't finish within the timeout
+  Timeout,
+}
+
+impl FromStr for Predicate {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "panic" => Ok(Predicate::Panic),
+      "mismatch" => Ok(Predicate::Mismatch),
+      "timeout" => Ok(Predicate::Timeout),
+      other => Err(format!("unknown failure predicate: {} (expected panic, mismatch or timeout)", other)),
+    }
+  }
+}
+
+// everything needed to decide if a candidate input still fails in the way we're interested in
+struct FailureCheck {
+  year: u16,
+  day: u8,
+  part: u8,
+  params: Params,
+  predicate: Predicate,
+  timeout: Duration,
+  // for the panic predicate, where the original panic happened
+  panic_location: Option<String>,
+  // for the mismatch predicate, which of the difftest cases to compare with
+  case_index: Option<usize>,
+}
+
+impl FailureCheck {
+  fn run(&self, input: &str) -> Outcome {
+    let year = self.year.to_string();
+    let day = self.day.to_string();
+    let part = self.part.to_string();
+    let params = self.params.to_args();
+    let mut args = vec!["solve-worker", "--year", &year, "--day", &day, "--part", &part];
+    args.extend(params.iter().map(|arg| arg.as_str()));
+    worker::run_in_worker(&args, input, self.timeout)
+  }
+
+  fn fails(&self, input: &str) -> bool {
+    match self.predicate {
+      Predicate::Panic => match self.run(input) {
+        Outcome::Panicked { location, .. } => Some(location) == self.panic_location,
+        _ => false,
+      },
+      Predicate::Timeout => self.run(input) == Outcome::TimedOut,
+      #[cfg(feature = "difftest")]
+      Predicate::Mismatch => difftest::compare(self.case_index.unwrap(), input, self.timeout).is_some(),
+      #[cfg(not(feature = "difftest"))]
+      Predicate::Mismatch => unreachable!(),
+    }
+  }
+}
+
+// The classic "ddmin" algorithm: split the pieces into n chunks and try removing each chunk in turn. If that
+// still fails, keep the smaller input and carry on with slightly coarser chunks; if not, split more finely.
+// Stops when even removing single pieces no longer fails.
+fn ddmin<T: Clone>(pieces: Vec<T>, still_fails: &dyn Fn(&[T]) -> bool) -> Vec<T> {
+  let mut pieces = pieces;
+  let mut granularity = 2;
+  while !pieces.is_empty() {
+    let chunk_size = pieces.len().div_ceil(granularity);
+    let mut reduced = false;
+    for start in (0..pieces.len()).step_by(chunk_size) {
+      let end = (start + chunk_size).min(pieces.len());
+      let complement: Vec<T> = pieces[..start].iter().chain(pieces[end..].iter()).cloned().collect();
+      if still_fails(&complement) {
+        pieces = complement;
+        granularity = (granularity - 1).max(2);
+        reduced = true;
+        break;
+      }
+    }
+    if !reduced {
+      if chunk_size == 1 {
+        break;
+      }
+      granularity = (granularity * 2).min(pieces.len());
+    }
+  }
+  pieces
+}
+
+fn minimize_sections(input: &str, check: &FailureCheck) -> String {
+  let sections: Vec<&str> = input.split("\n\n").collect();
+  ddmin(sections, &|sections| check.fails(&sections.join("\n\n"))).join("\n\n")
+}
+
+// minimizes the lines of each section separately, so that the blank lines between sections stay put
+fn minimize_lines_within_sections(input: &str, check: &FailureCheck) -> String {
+  let mut sections: Vec<String> = input.split("\n\n").map(|section| section.to_owned()).collect();
+  for index in 0..sections.len() {
+    let lines: Vec<String> = sections[index].lines().map(|line| line.to_owned()).collect();
+    let minimized = ddmin(lines, &|lines| {
+      let mut candidate = sections.clone();
+      candidate[index] = lines.join("\n");
+      check.fails(&candidate.join("\n\n"))
+    });
+    sections[index] = minimized.join("\n");
+  }
+  sections.join("\n\n")
+}
+
+fn minimize_lines(input: &str, check: &FailureCheck) -> String {
+  let lines: Vec<&str> = input.lines().collect();
+  ddmin(lines, &|lines| check.fails(&lines.join("\n"))).join("\n")
+}
+
+fn minimize_grid_columns(input: &str, check: &FailureCheck) -> String {
+  let rows: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+  let width = rows.first().map_or(0, |row| row.len());
+  let with_columns = |columns: &[usize]| -> String {
+    rows.iter().map(|row| columns.iter().filter_map(|&column| row.get(column)).collect::<String>()).collect::<Vec<String>>().join("\n")
+  };
+  let columns = ddmin((0..width).collect(), &|columns| check.fails(&with_columns(columns)));
+  with_columns(&columns)
+}
+
+// removes tokens from within each line, where the tokens are separated by the given string
+fn minimize_tokens(input: &str, separator: &str, check: &FailureCheck) -> String {
+  let mut lines: Vec<String> = input.split('\n').map(|line| line.to_owned()).collect();
+  for index in 0..lines.len() {
+    let tokens: Vec<String> = lines[index].split(separator).map(|token| token.to_owned()).collect();
+    let minimized = ddmin(tokens, &|tokens| {
+      let mut candidate = lines.clone();
+      candidate[index] = tokens.join(separator);
+      check.fails(&candidate.join("\n"))
+    });
+    lines[index] = minimized.join(separator);
+  }
+  lines.join("\n")
+}
+
+// one round of every kind of reduction that makes sense for the shape of input
+fn minimize_once(input: &str, shape: InputShape, check: &FailureCheck) -> String {
+  match shape {
+    InputShape::Sections => {
+      let input = minimize_sections(input, check);
+      let input = minimize_lines_within_sections(&input, check);
+      minimize_tokens(&input, " ", check)
+    },
+    InputShape::Lines => {
+      let input = minimize_lines(input, check);
+      minimize_tokens(&input, " ", check)
+    },
+    InputShape::Grid => {
+      let input = minimize_lines(input, check);
+      minimize_grid_columns(&input, check)
+    },
+    InputShape::CommaSeparated => minimize_tokens(input.trim_end(), ",", check),
+  }
+}
+
+// entry point for the `minimize` command. Returns the smallest failing input found, or an error if the
+// original input doesn't fail in the first place.
+pub fn minimize(day: &Day, part: u8, input: &str, params: Params, predicate: Predicate, timeout: Duration) -> Result<String, String> {
+  if day.part(part).is_none() {
+    return Err(format!("day {} has no part {}", day.number, part));
+  }
+
+  let mut check = FailureCheck { year: day.year, day: day.number, part, params, predicate, timeout, panic_location: None, case_index: None };
+  match predicate {
+    Predicate::Panic => match check.run(input) {
+      Outcome::Panicked { location, .. } => check.panic_location = Some(location),
+      other => return Err(format!("the input doesn't make the solver panic (it gave {})", other.describe())),
+    },
+    #[cfg(not(feature = "difftest"))]
+    Predicate::Mismatch => return Err(String::from("the mismatch predicate needs the difftest feature, which this build doesn't have")),
+    #[cfg(feature = "difftest")]
+    Predicate::Mismatch => {
+      check.case_index = difftest::find_case(day.year, day.number, part);
+      if check.case_index.is_none() {
+        return Err(format!("there is no reference solver for {} day {}, part {}", day.year, day.number, part));
+      }
+    },
+    Predicate::Timeout => (),
+  }
+  if !check.fails(input) {
+    return Err(String::from("the input doesn't fail in the way given"));
+  }
+
+  // keep going round until a whole round doesn't manage to remove anything
+  let mut current = input.to_owned();
+  loop {
+    let next = minimize_once(&current, day.shape, &check);
+    if next.len() >= current.len() {
+      return Ok(current);
+    }
+    current = next;
+  }
+}
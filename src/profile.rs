@@ -0,0 +1,98 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Finding out where a solution spends its effort, for the `bench` command, in two ways:
+// - counting memory allocations (and the bytes asked for), with a global allocator wrapped round the system one.
+//   This slows down every single allocation a little, so is only there when built with the `count-allocations`
+//   feature.
+// - named counters that the solutions bump themselves - nodes expanded, states memoised, clones made and so on.
+//   Like the tracing, these cost no more than checking a flag unless something is looking at them.
+
+#[cfg(feature = "count-allocations")]
+mod counting_allocator {
+  use std::alloc::{GlobalAlloc, Layout, System};
+  use std::sync::atomic::{AtomicU64, Ordering};
+
+  pub static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+  pub static BYTES: AtomicU64 = AtomicU64::new(0);
+
+  pub struct CountingAllocator;
+
+  // everything is passed straight on to the system allocator - a realloc counts as a new allocation of the new size,
+  // as that's usually what it costs
+  unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+      ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+      BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+      System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+      ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+      BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+      System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+      ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+      BYTES.fetch_add(new_size as u64, Ordering::Relaxed);
+      System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+      System.dealloc(ptr, layout)
+    }
+  }
+
+  #[global_allocator]
+  static ALLOCATOR: CountingAllocator = CountingAllocator;
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct Allocations {
+  pub count: u64,
+  pub bytes: u64,
+}
+
+// the allocations made since the program started, if they're being counted
+pub fn allocations() -> Option<Allocations> {
+  #[cfg(feature = "count-allocations")]
+  {
+    use counting_allocator::{ALLOCATIONS, BYTES};
+    Some(Allocations { count: ALLOCATIONS.load(Ordering::Relaxed), bytes: BYTES.load(Ordering::Relaxed) })
+  }
+  #[cfg(not(feature = "count-allocations"))]
+  None
+}
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+
+// The counters are kept per thread, so that bumping one doesn't need a lock - which is fine as the solutions all
+// run on a single thread.
+thread_local! {
+  static COUNTERS: RefCell<BTreeMap<&'static str, u64>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+pub fn add(name: &'static str, amount: u64) {
+  if !COUNTING.load(Ordering::Relaxed) {
+    return;
+  }
+  COUNTERS.with(|counters| *counters.borrow_mut().entry(name).or_insert(0) += amount);
+}
+
+pub fn count(name: &'static str) {
+  add(name, 1);
+}
+
+// starts the counters from nothing
+pub fn start_counting() {
+  COUNTERS.with(|counters| counters.borrow_mut().clear());
+  COUNTING.store(true, Ordering::Relaxed);
+}
+
+// stops counting, and gives the counts since `start_counting`, in alphabetical order
+pub fn stop_counting() -> Vec<(&'static str, u64)> {
+  COUNTING.store(false, Ordering::Relaxed);
+  COUNTERS.with(|counters| std::mem::take(&mut *counters.borrow_mut()).into_iter().collect())
+}
@@ -0,0 +1,208 @@
+use crate::batch::{self, BatchResult};
+use crate::solutions::{all_days, Day, Params};
+use std::panic;
+use std::str::FromStr;
+use std::time::Duration;
+
+// The `report` command: solves every day of a year and makes a single file to share with the answers, how long
+// each part took, whether the solutions' assumptions about the input held up, and pictures for the days that
+// have them. Each part is solved in a worker process of its own, exactly as in batch mode. The report is either
+// HTML or Markdown, with the pictures inline in both, so that nothing else needs to go alongside it.
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Format {
+  Html,
+  Markdown,
+}
+
+impl FromStr for Format {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "html" => Ok(Format::Html),
+      "markdown" => Ok(Format::Markdown),
+      other => Err(format!("unknown report format: {} (expected html or markdown)", other)),
+    }
+  }
+}
+
+impl Format {
+  pub fn extension(&self) -> &'static str {
+    match self {
+      Format::Html => "html",
+      Format::Markdown => "md",
+    }
+  }
+}
+
+struct DayReport {
+  number: u8,
+  // why the day couldn't be run at all, if it couldn't
+  missing_input: Option<String>,
+  parts: Vec<(u8, BatchResult)>,
+  // the picture as SVG (or why it couldn't be drawn), for the days that have one
+  picture: Option<Result<String, String>>,
+}
+
+fn run_day(day: &Day, input: Result<String, String>, timeout: Duration) -> DayReport {
+  let mut report = DayReport { number: day.number, missing_input: None, parts: vec![], picture: None };
+  let input = match input {
+    Ok(input) => input,
+    Err(message) => {
+      eprintln!("day {}: {}", day.number, message);
+      report.missing_input = Some(message);
+      return report;
+    },
+  };
+  for part in [1, 2] {
+    if day.part(part).is_some() {
+      let result = batch::solve_in_worker(day, part, &Params::default(), format!("part {}", part), &input, timeout);
+      eprintln!("day {}, part {}: {}", day.number, part, result.problem().unwrap_or(format_time(result.time)));
+      report.parts.push((part, result));
+    }
+  }
+  // the pictures are drawn here rather than in a worker, so a panic is caught instead
+  if let Some(render) = day.render {
+    report.picture = Some(panic::catch_unwind(|| render(&input).to_svg()).map_err(|error| {
+      error.downcast_ref::<String>().cloned().or(error.downcast_ref::<&str>().map(|message| message.to_string())).unwrap_or_default()
+    }));
+  }
+  report
+}
+
+fn escape_html(text: &str) -> String {
+  text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// the standard base64 encoding, for putting the pictures in data URLs in the Markdown
+fn base64(bytes: &[u8]) -> String {
+  const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+  let mut result = String::new();
+  for chunk in bytes.chunks(3) {
+    let value = chunk.iter().enumerate().fold(0u32, |value, (index, &byte)| value | (byte as u32) << (16 - 8 * index));
+    for index in 0..4 {
+      if index <= chunk.len() {
+        result.push(ALPHABET[(value >> (18 - 6 * index)) as usize & 63] as char);
+      } else {
+        result.push('=');
+      }
+    }
+  }
+  result
+}
+
+fn format_time(time: Duration) -> String {
+  if time.as_secs() >= 1 {
+    format!("{:.1}s", time.as_secs_f64())
+  } else {
+    format!("{:.1}ms", time.as_secs_f64() * 1000.0)
+  }
+}
+
+// one row of the table for each part: day, part, answer, time and status
+fn rows(days: &[DayReport]) -> Vec<[String; 5]> {
+  let mut rows = vec![];
+  for day in days {
+    if let Some(message) = &day.missing_input {
+      rows.push([day.number.to_string(), String::from("-"), String::from("-"), String::from("-"), format!("no input: {}", message)]);
+    }
+    for (part, result) in day.parts.iter() {
+      rows.push([
+        day.number.to_string(),
+        part.to_string(),
+        result.answer.clone().unwrap_or(String::from("-")),
+        format_time(result.time),
+        result.problem().unwrap_or(String::from("ok")),
+      ]);
+    }
+  }
+  rows
+}
+
+fn summary(days: &[DayReport]) -> String {
+  let results: Vec<&BatchResult> = days.iter().flat_map(|day| day.parts.iter().map(|(_, result)| result)).collect();
+  let solved = results.iter().filter(|result| result.problem().is_none()).count();
+  let total_time: Duration = results.iter().map(|result| result.time).sum();
+  let missing = days.iter().filter(|day| day.missing_input.is_some()).count();
+  let mut summary = format!("{} of {} parts solved with no problems, in {} altogether.", solved, results.len(), format_time(total_time));
+  if missing > 0 {
+    summary.push_str(&format!(" {} days couldn't be run as they had no input.", missing));
+  }
+  summary
+}
+
+fn to_html(year: u16, days: &[DayReport]) -> String {
+  let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+  html.push_str(&format!("<title>Advent of Code {}</title>\n", year));
+  html.push_str("<style>\n");
+  html.push_str("body { font-family: sans-serif; max-width: 60em; margin: 2em auto; }\n");
+  html.push_str("table { border-collapse: collapse; }\n");
+  html.push_str("th, td { border: 1px solid #ccc; padding: 0.25em 0.75em; text-align: left; }\n");
+  html.push_str("td.number { text-align: right; font-family: monospace; }\n");
+  html.push_str(".ok { color: #228822; }\n.problem { color: #cc2222; }\n");
+  html.push_str("</style>\n</head>\n<body>\n");
+  html.push_str(&format!("<h1>Advent of Code {}</h1>\n<p>{}</p>\n", year, escape_html(&summary(days))));
+
+  html.push_str("<table>\n<tr><th>day</th><th>part</th><th>answer</th><th>time</th><th>status</th></tr>\n");
+  for [day, part, answer, time, status] in rows(days) {
+    let class = if status == "ok" { "ok" } else { "problem" };
+    html.push_str(&format!(
+      "<tr><td class=\"number\">{}</td><td class=\"number\">{}</td><td class=\"number\">{}</td><td class=\"number\">{}</td><td class=\"{}\">{}</td></tr>\n",
+      day, part, escape_html(&answer), time, class, escape_html(&status)
+    ));
+  }
+  html.push_str("</table>\n");
+
+  let pictures: Vec<(u8, &Result<String, String>)> = days.iter().filter_map(|day| day.picture.as_ref().map(|picture| (day.number, picture))).collect();
+  if !pictures.is_empty() {
+    html.push_str("<h2>Pictures</h2>\n");
+  }
+  for (number, picture) in pictures {
+    html.push_str(&format!("<h3>Day {}</h3>\n", number));
+    match picture {
+      // SVG can go straight into an HTML page
+      Ok(svg) => html.push_str(&format!("<div>{}</div>\n", svg)),
+      Err(message) => html.push_str(&format!("<p class=\"problem\">couldn't draw the picture: {}</p>\n", escape_html(message))),
+    }
+  }
+  html.push_str("</body>\n</html>\n");
+  html
+}
+
+fn to_markdown(year: u16, days: &[DayReport]) -> String {
+  let mut markdown = format!("# Advent of Code {}\n\n{}\n\n", year, summary(days));
+  markdown.push_str("| day | part | answer | time | status |\n|----:|-----:|-------:|-----:|--------|\n");
+  for row in rows(days) {
+    let cells: Vec<String> = row.iter().map(|cell| cell.replace('|', "\\|")).collect();
+    markdown.push_str(&format!("| {} |\n", cells.join(" | ")));
+  }
+
+  let pictures: Vec<(u8, &Result<String, String>)> = days.iter().filter_map(|day| day.picture.as_ref().map(|picture| (day.number, picture))).collect();
+  if !pictures.is_empty() {
+    markdown.push_str("\n## Pictures\n");
+  }
+  for (number, picture) in pictures {
+    markdown.push_str(&format!("\n### Day {}\n\n", number));
+    match picture {
+      // Markdown can't have SVG in it directly, but it can have an image whose URL is the SVG itself
+      Ok(svg) => markdown.push_str(&format!("![day {}](data:image/svg+xml;base64,{})\n", number, base64(svg.as_bytes()))),
+      Err(message) => markdown.push_str(&format!("couldn't draw the picture: {}\n", message)),
+    }
+  }
+  markdown
+}
+
+// Entry point for the `report` command - runs everything and gives the report's contents. The input for each day
+// comes from `get_input`, which gives an error if there isn't one.
+pub fn build(year: u16, format: Format, timeout: Duration, get_input: &dyn Fn(&Day) -> Result<String, String>) -> Result<String, String> {
+  let days = all_days(year);
+  if days.is_empty() {
+    return Err(format!("no solutions for {} in this build", year));
+  }
+  let reports: Vec<DayReport> = days.iter().map(|day| run_day(day, get_input(day), timeout)).collect();
+  Ok(match format {
+    Format::Html => to_html(year, &reports),
+    Format::Markdown => to_markdown(year, &reports),
+  })
+}
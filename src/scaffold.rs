@@ -0,0 +1,301 @@
+use std::fs;
+use std::path::Path;
+
+// Sets up a new day's solution: creates the module for it (and for the year, if it's the first day of a new
+// year) with the usual skeleton, and adds it everywhere it needs to be mentioned - the `mod` declarations, the
+// year's `run` function, the registry and the day's feature in Cargo.toml. It edits the files as text, so relies on
+// them being laid out the way the templates below (and the features in Cargo.toml) lay them out.
+
+const SOLUTIONS_DIR: &str = "./src/solutions";
+const MANIFEST_PATH: &str = "./Cargo.toml";
+
+const YEAR_TEMPLATE: &str = "
+use super::{Day, InputShape};
+
+// the original way of getting the answers - run everything, one after the other
+pub fn run() {
+}
+
+// the table of solutions for the registry (see the parent module) - just the days that were compiled in
+pub fn days() -> Vec<Day> {
+  vec![
+  ]
+}
+";
+
+const DAY_TEMPLATE: &str = "use crate::solutions::reader;
+
+fn parse_input(contents: &str) -> Vec<String> {
+  let contents: &str = &reader::normalize(contents);
+  contents.lines().map(|line| line.to_owned()).collect()
+}
+
+fn read_file() -> Vec<String> {
+  parse_input(&reader::read({year}, {day}))
+}
+
+fn solve_part_1(lines: &[String]) -> usize {
+  // TODO - this just counts the lines for now
+  lines.len()
+}
+
+pub fn part_1() -> usize {
+  let lines = read_file();
+  solve_part_1(&lines)
+}
+
+pub fn part_1_from_input(input: &str) -> usize {
+  let lines = parse_input(input);
+  solve_part_1(&lines)
+}
+
+fn solve_part_2(lines: &[String]) -> usize {
+  // TODO - this just counts the lines for now
+  lines.len()
+}
+
+pub fn part_2() -> usize {
+  let lines = read_file();
+  solve_part_2(&lines)
+}
+
+pub fn part_2_from_input(input: &str) -> usize {
+  let lines = parse_input(input);
+  solve_part_2(&lines)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // the example from the puzzle text
+  const EXAMPLE: &str = \"\";
+
+  #[test]
+  #[ignore = \"fill in the example and its answer\"]
+  fn part_1_example() {
+    assert_eq!(part_1_from_input(EXAMPLE), 0);
+  }
+
+  #[test]
+  #[ignore = \"fill in the example and its answer\"]
+  fn part_2_example() {
+    assert_eq!(part_2_from_input(EXAMPLE), 0);
+  }
+}
+";
+
+const RUN_TEMPLATE: &str = "  #[cfg(feature = \"year{year}-day{day}\")]
+  {
+    println!(\"The answer to day {day}, part 1 is {}\", day{day}::part_1());
+    println!(\"The answer to day {day}, part 2 is {}\", day{day}::part_2());
+  }
+";
+
+const REGISTRY_TEMPLATE: &str = "    #[cfg(feature = \"year{year}-day{day}\")]
+    Day {
+      year: {year},
+      number: {day},
+      shape: InputShape::{shape},
+      params: &[],
+      check_params: None,
+      part_1: |input, _| day{day}::part_1_from_input(input).to_string(),
+      part_2: Some(|input, _| day{day}::part_2_from_input(input).to_string()),
+      render: None,
+    },
+";
+
+fn fill_in(template: &str, year: u16, day: u8, shape: &str) -> String {
+  template.replace("{year}", &year.to_string()).replace("{day}", &day.to_string()).replace("{shape}", shape)
+}
+
+fn shape_name(shape: &str) -> Result<&'static str, String> {
+  match shape {
+    "lines" => Ok("Lines"),
+    "grid" => Ok("Grid"),
+    "sections" => Ok("Sections"),
+    "comma-separated" => Ok("CommaSeparated"),
+    other => Err(format!("unknown input shape: {} (expected lines, grid, sections or comma-separated)", other)),
+  }
+}
+
+// inserts text just before the first line starting with `last_line` after the (whole) lines given by `opening`
+fn insert_at_end_of_block(contents: &str, opening: &str, last_line: &str, text: &str) -> Result<String, String> {
+  let start = contents.find(opening).ok_or(format!("couldn't find \"{}\"", opening))? + opening.len();
+  let closing = format!("\n{}", last_line);
+  let end = start + contents[start..].find(&closing).ok_or(format!("couldn't find the end of \"{}\"", opening))? + 1;
+  Ok(format!("{}{}{}", &contents[..end], text, &contents[end..]))
+}
+
+// the day of the year that a line is about, going by the day's feature name in it - as in the `#[cfg]` line
+// before each day in the year's module, or the day's line in Cargo.toml
+fn day_mentioned(line: &str, year: u16) -> Option<u8> {
+  let prefix = format!("year{}-day", year);
+  let rest = &line[line.find(&prefix)? + prefix.len()..];
+  let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+  rest[..digits].parse().ok()
+}
+
+// Inserts text into the block given (as for `insert_at_end_of_block`), keeping the days in it in numerical
+// order: just before the first line about a later day, or at the end if there isn't one.
+fn insert_in_day_order(contents: &str, opening: &str, last_line: &str, year: u16, day: u8, text: &str) -> Result<String, String> {
+  let start = contents.find(opening).ok_or(format!("couldn't find \"{}\"", opening))? + opening.len();
+  let closing = format!("\n{}", last_line);
+  let end = start + contents[start..].find(&closing).ok_or(format!("couldn't find the end of \"{}\"", opening))? + 1;
+  let mut offset = start;
+  for line in contents[start..end].split_inclusive('\n') {
+    if day_mentioned(line, year).is_some_and(|other| other > day) {
+      return Ok(format!("{}{}{}", &contents[..offset], text, &contents[offset..]));
+    }
+    offset += line.len();
+  }
+  Ok(format!("{}{}{}", &contents[..end], text, &contents[end..]))
+}
+
+// Adds `pub mod <prefix><number>;` among the others with the same prefix, keeping them in numerical order - only
+// compiled in with the feature given, if there is one. It goes after the last one that's smaller, or else before
+// the first (and any attributes on it), and if there aren't any yet, at the very top.
+fn add_mod_declaration(contents: &str, prefix: &str, number: u32, feature: Option<&str>) -> String {
+  let mut declaration = format!("pub mod {}{};\n", prefix, number);
+  if let Some(feature) = feature {
+    declaration = format!("#[cfg(feature = \"{}\")]\n{}", feature, declaration);
+  }
+  let (mut after_smaller, mut before_larger) = (None, None);
+  // where the attributes on the line coming up started, if there are any
+  let mut attributes_start = None;
+  let mut offset = 0;
+  for line in contents.split_inclusive('\n') {
+    let line_start = offset;
+    offset += line.len();
+    if line.starts_with("#[") {
+      attributes_start = attributes_start.or(Some(line_start));
+      continue;
+    }
+    let existing = line.strip_prefix("pub mod ").and_then(|rest| rest.strip_prefix(prefix)).and_then(|rest| rest.trim_end().strip_suffix(';'));
+    if let Some(existing) = existing.and_then(|existing| existing.parse::<u32>().ok()) {
+      if existing < number {
+        after_smaller = Some(offset);
+      } else if before_larger.is_none() {
+        before_larger = Some(attributes_start.unwrap_or(line_start));
+      }
+    }
+    attributes_start = None;
+  }
+  let position = after_smaller.or(before_larger).unwrap_or(0);
+  format!("{}{}{}", &contents[..position], declaration, &contents[position..])
+}
+
+// hooks a brand new year module up to the registry
+fn register_year(year: u16) -> Result<(), String> {
+  let path = Path::new(SOLUTIONS_DIR).join("mod.rs");
+  let contents = fs::read_to_string(&path).map_err(|error| error.to_string())?;
+  let contents = add_mod_declaration(&contents, "year", year as u32, None);
+  let contents = insert_at_end_of_block(&contents, "pub fn all_days(year: u16) -> Vec<Day> {\n  match year {", "    _ =>", &format!("    {} => year{}::days(),\n", year, year))?;
+  let contents = insert_at_end_of_block(&contents, "pub fn run(year: u16) {\n  match year {", "    _ =>", &format!("    {} => year{}::run(),\n", year, year))?;
+  fs::write(&path, contents).map_err(|error| error.to_string())
+}
+
+// Gives the new day a feature of its own in Cargo.toml, as part of its year's feature - which for a new year is
+// added too, and turned on by default.
+fn add_feature(year: u16, day: u8, new_year: bool) -> Result<(), String> {
+  let mut contents = fs::read_to_string(MANIFEST_PATH).map_err(|error| error.to_string())?;
+  let year_feature = format!("year{}", year);
+  let day_feature = format!("{}-day{}", year_feature, day);
+  if new_year {
+    let default_end = contents.find("\ndefault = [").and_then(|start| contents[start..].find(']').map(|end| start + end));
+    let default_end = default_end.ok_or(String::from("couldn't find the default features"))?;
+    contents.insert_str(default_end, &format!(", \"{}\"", year_feature));
+    contents.push_str(&format!("\n{} = [\n]\n", year_feature));
+  }
+  let opening = format!("\n{} = [", year_feature);
+  contents = insert_in_day_order(&contents, &opening, "]", year, day, &format!("  \"{}\",\n", day_feature))?;
+
+  // the day's own feature goes among the others for the year, which come straight after the year's - before the
+  // first later day, or else after the last of them
+  let block_end = contents.find(&opening).and_then(|start| contents[start..].find("\n]\n").map(|end| start + end + 3)).unwrap();
+  let mut position = block_end;
+  for line in contents[block_end..].split_inclusive('\n') {
+    if !line.starts_with(&format!("{}-day", year_feature)) || day_mentioned(line, year).is_some_and(|other| other > day) {
+      break;
+    }
+    position += line.len();
+  }
+  contents.insert_str(position, &format!("{} = []\n", day_feature));
+  fs::write(MANIFEST_PATH, contents).map_err(|error| error.to_string())
+}
+
+// Entry point for the `scaffold` command - returns the files created or changed. This has to be run from the
+// root of the repository, as that's where the source files are looked for.
+pub fn scaffold(year: u16, day: u8, shape: &str) -> Result<Vec<String>, String> {
+  if !(2015..=2100).contains(&year) || !(1..=25).contains(&day) {
+    return Err(format!("there's no puzzle for {} day {}", year, day));
+  }
+  let shape = shape_name(shape)?;
+  if !Path::new(SOLUTIONS_DIR).is_dir() || !Path::new(MANIFEST_PATH).is_file() {
+    return Err(format!("couldn't find {} - run this from the root of the repository", SOLUTIONS_DIR));
+  }
+
+  let year_dir = Path::new(SOLUTIONS_DIR).join(format!("year{}", year));
+  let day_path = year_dir.join(format!("day{}.rs", day));
+  let year_path = year_dir.join("mod.rs");
+  if day_path.exists() {
+    return Err(format!("{} already exists", day_path.display()));
+  }
+  let mut changed = vec![];
+
+  let new_year = !year_path.exists();
+  if new_year {
+    fs::create_dir_all(&year_dir).map_err(|error| error.to_string())?;
+    fs::write(&year_path, YEAR_TEMPLATE).map_err(|error| error.to_string())?;
+    register_year(year)?;
+    changed.push(format!("{}/mod.rs", SOLUTIONS_DIR));
+  }
+
+  let contents = fs::read_to_string(&year_path).map_err(|error| error.to_string())?;
+  let feature = format!("year{}-day{}", year, day);
+  let contents = add_mod_declaration(&contents, "day", day as u32, Some(&feature));
+  let contents = insert_in_day_order(&contents, "pub fn run() {", "}", year, day, &fill_in(RUN_TEMPLATE, year, day, shape))?;
+  let registry = fill_in(REGISTRY_TEMPLATE, year, day, shape);
+  let contents = insert_in_day_order(&contents, "pub fn days() -> Vec<Day> {\n  vec![", "  ]", year, day, &registry)?;
+  fs::write(&year_path, contents).map_err(|error| error.to_string())?;
+  changed.push(year_path.display().to_string());
+
+  fs::write(&day_path, fill_in(DAY_TEMPLATE, year, day, shape)).map_err(|error| error.to_string())?;
+  changed.push(day_path.display().to_string());
+
+  add_feature(year, day, new_year)?;
+  changed.push(MANIFEST_PATH.to_owned());
+
+  // somewhere for the input to go
+  let input_dir = format!("./input/{}", year);
+  fs::create_dir_all(&input_dir).map_err(|error| error.to_string())?;
+
+  Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn mod_declarations_go_in_numerical_order() {
+    let contents = "#[cfg(feature = \"year2023-day2\")]\npub mod day2;\n#[cfg(feature = \"year2023-day10\")]\npub mod day10;\n";
+    let middle = add_mod_declaration(contents, "day", 5, Some("year2023-day5"));
+    assert_eq!(middle.lines().filter(|line| line.starts_with("pub mod")).collect::<Vec<&str>>(), ["pub mod day2;", "pub mod day5;", "pub mod day10;"]);
+    assert!(middle.contains("pub mod day2;\n#[cfg(feature = \"year2023-day5\")]\npub mod day5;\n#[cfg(feature = \"year2023-day10\")]"));
+    let first = add_mod_declaration("pub mod picture;\npub mod year2023;\n", "year", 2022, None);
+    assert_eq!(first, "pub mod picture;\npub mod year2022;\npub mod year2023;\n");
+    assert_eq!(add_mod_declaration("\nuse super::Day;\n", "day", 1, None), "pub mod day1;\n\nuse super::Day;\n");
+  }
+
+  #[test]
+  fn days_are_inserted_before_any_later_ones() {
+    let contents = "year2023 = [\n  \"year2023-day3\",\n  \"year2023-day12\",\n]\n";
+    let early = insert_in_day_order(contents, "year2023 = [", "]", 2023, 1, "  \"year2023-day1\",\n").unwrap();
+    assert_eq!(early, "year2023 = [\n  \"year2023-day1\",\n  \"year2023-day3\",\n  \"year2023-day12\",\n]\n");
+    let middle = insert_in_day_order(contents, "year2023 = [", "]", 2023, 4, "  \"year2023-day4\",\n").unwrap();
+    assert_eq!(middle, "year2023 = [\n  \"year2023-day3\",\n  \"year2023-day4\",\n  \"year2023-day12\",\n]\n");
+    let last = insert_in_day_order(contents, "year2023 = [", "]", 2023, 20, "  \"year2023-day20\",\n").unwrap();
+    assert_eq!(last, "year2023 = [\n  \"year2023-day3\",\n  \"year2023-day12\",\n  \"year2023-day20\",\n]\n");
+  }
+}
@@ -0,0 +1,258 @@
+use crate::solutions::{get_day, Day, Params};
+use crate::worker::{self, Outcome};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// A very small HTTP/1.1 server so that people without a Rust toolchain can get answers for their own input.
+// There's just one endpoint:
+//
+//   POST /{year}/day/{n}/part/{p}   with the puzzle input as the body
+//
+// which responds with JSON like {"year": 2023, "day": 5, "part": 2, "answer": "46", "time_ms": 12, "error": null}.
+// The year can be left out of the URL, to get the year the server was started with. Any of the
+// puzzle parameters (see `Params`) can be given in the query string, eg. /day/21/part/1?part-1-steps=6, and
+// anything else there is a 400.
+// Each request is solved in its own worker process, so that a solver which panics (usually because the input
+// isn't in the expected format) or takes too long can't bring the server down with it.
+
+pub struct Config {
+  pub year: u16,
+  pub port: u16,
+  pub max_body_bytes: usize,
+  pub timeout: Duration,
+}
+
+// the request line and headers together can't be any longer than this
+const MAX_HEADER_BYTES: usize = 8 * 1024;
+
+// how long to wait for a slow client before giving up on it
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+struct Request {
+  method: String,
+  path: String,
+  query: String,
+  body: String,
+}
+
+struct Response {
+  status: u16,
+  body: String,
+}
+
+fn status_text(status: u16) -> &'static str {
+  match status {
+    200 => "OK",
+    400 => "Bad Request",
+    404 => "Not Found",
+    405 => "Method Not Allowed",
+    408 => "Request Timeout",
+    411 => "Length Required",
+    413 => "Payload Too Large",
+    422 => "Unprocessable Entity",
+    431 => "Request Header Fields Too Large",
+    504 => "Gateway Timeout",
+    _ => "Internal Server Error",
+  }
+}
+
+fn json_string(s: &str) -> String {
+  let mut escaped = String::from("\"");
+  for c in s.chars() {
+    match c {
+      '"' => escaped.push_str("\\\""),
+      '\\' => escaped.push_str("\\\\"),
+      '\n' => escaped.push_str("\\n"),
+      '\r' => escaped.push_str("\\r"),
+      '\t' => escaped.push_str("\\t"),
+      c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+      c => escaped.push(c),
+    }
+  }
+  escaped.push('"');
+  escaped
+}
+
+fn json_or_null(value: Option<&str>) -> String {
+  value.map_or(String::from("null"), json_string)
+}
+
+fn error_response(status: u16, message: &str) -> Response {
+  Response { status, body: format!("{{\"error\": {}}}", json_string(message)) }
+}
+
+// reads a single line of the request head, counting its length against the header limit
+fn read_head_line(reader: &mut BufReader<&TcpStream>, bytes_read: &mut usize) -> Result<String, Response> {
+  let mut line = vec![];
+  let limit = (MAX_HEADER_BYTES - *bytes_read) as u64 + 1;
+  match reader.by_ref().take(limit).read_until(b'\n', &mut line) {
+    Ok(_) => (),
+    Err(_) => return Err(error_response(408, "timed out reading the request")),
+  }
+  *bytes_read += line.len();
+  if *bytes_read > MAX_HEADER_BYTES {
+    return Err(error_response(431, "request headers are too large"));
+  }
+  if !line.ends_with(b"\n") {
+    return Err(error_response(400, "connection closed before the end of the request headers"));
+  }
+  String::from_utf8(line).map(|line| line.trim_end().to_owned()).map_err(|_| error_response(400, "request headers aren't valid UTF-8"))
+}
+
+fn read_request(stream: &TcpStream, max_body_bytes: usize) -> Result<Request, Response> {
+  let mut reader = BufReader::new(stream);
+  let mut bytes_read = 0;
+
+  let request_line = read_head_line(&mut reader, &mut bytes_read)?;
+  let parts: Vec<&str> = request_line.split(' ').collect();
+  if parts.len() != 3 || !parts[2].starts_with("HTTP/1.") {
+    return Err(error_response(400, "malformed request line"));
+  }
+  let method = parts[0].to_owned();
+  let (path, query) = parts[1].split_once('?').unwrap_or((parts[1], ""));
+
+  let mut content_length = None;
+  loop {
+    let header = read_head_line(&mut reader, &mut bytes_read)?;
+    if header.is_empty() {
+      break;
+    }
+    let (name, value) = header.split_once(':').ok_or_else(|| error_response(400, "malformed header"))?;
+    if name.eq_ignore_ascii_case("content-length") {
+      let length: usize = value.trim().parse().map_err(|_| error_response(400, "invalid Content-Length"))?;
+      content_length = Some(length);
+    } else if name.eq_ignore_ascii_case("transfer-encoding") {
+      return Err(error_response(411, "chunked requests aren't supported - please send a Content-Length"));
+    }
+  }
+
+  let body = match (method.as_str(), content_length) {
+    ("POST", None) => return Err(error_response(411, "a Content-Length is required")),
+    (_, Some(length)) if length > max_body_bytes => {
+      return Err(error_response(413, &format!("the input can be at most {} bytes", max_body_bytes)));
+    },
+    (_, Some(length)) => {
+      let mut body = vec![0; length];
+      reader.read_exact(&mut body).map_err(|_| error_response(400, "the body is shorter than its Content-Length"))?;
+      String::from_utf8(body).map_err(|_| error_response(400, "the input isn't valid UTF-8"))?
+    },
+    (_, None) => String::new(),
+  };
+
+  Ok(Request { method, path: path.to_owned(), query: query.to_owned(), body })
+}
+
+// Turns `?part-1-steps=6&part-2-steps=100` into the same `--part-1-steps 6 --part-2-steps 100` arguments the
+// command line takes. Unlike on the command line, a name that isn't one of the day's parameters (probably a typo)
+// is an error rather than being ignored, as is one without a value.
+fn query_to_args(query: &str, day: &Day) -> Result<Vec<String>, String> {
+  let mut args = vec![];
+  for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+    let Some((name, value)) = pair.split_once('=') else {
+      return Err(format!("no value given for {} in the query string", pair));
+    };
+    if !day.params.iter().any(|param| param.name == name) {
+      let names: Vec<&str> = day.params.iter().map(|param| param.name).collect();
+      let expected = if names.is_empty() { String::from("this day has none") } else { format!("expected {}", names.join(", ")) };
+      return Err(format!("unknown parameter {} ({})", name, expected));
+    }
+    args.extend([format!("--{}", name), value.to_owned()]);
+  }
+  Ok(args)
+}
+
+fn solve(request: &Request, year: u16, day_number: u8, part: u8, timeout: Duration) -> Response {
+  let day = match get_day(year, day_number) {
+    Some(day) => day,
+    None => return error_response(404, &format!("no solution for {} day {}", year, day_number)),
+  };
+  if day.part(part).is_none() {
+    return error_response(404, &format!("{} day {} has no part {}", year, day_number, part));
+  }
+
+  let params = match query_to_args(&request.query, &day).and_then(|args| Params::from_args(&args, &day)) {
+    Ok(params) => params,
+    Err(message) => return error_response(400, &message),
+  };
+  let year_arg = year.to_string();
+  let day_arg = day_number.to_string();
+  let part_arg = part.to_string();
+  let param_args = params.to_args();
+  let mut args = vec!["solve-worker", "--year", &year_arg, "--day", &day_arg, "--part", &part_arg];
+  args.extend(param_args.iter().map(|arg| arg.as_str()));
+
+  // note that this includes the time taken to start up the worker process, which is typically a millisecond or two
+  let start = Instant::now();
+  let outcome = worker::run_in_worker(&args, &request.body, timeout);
+  let time_ms = start.elapsed().as_millis();
+
+  let (status, answer, error) = match &outcome {
+    Outcome::Answer(answer) => (200, Some(answer.clone()), None),
+    // the solvers signal input they can't handle by panicking
+    Outcome::Panicked { message, .. } => (422, None, Some(format!("couldn't solve with this input: {}", message))),
+    Outcome::TimedOut => (504, None, Some(format!("the solver didn't finish within {}ms", timeout.as_millis()))),
+  };
+  let body = format!(
+    "{{\"year\": {}, \"day\": {}, \"part\": {}, \"answer\": {}, \"time_ms\": {}, \"error\": {}}}",
+    year,
+    day_number,
+    part,
+    json_or_null(answer.as_deref()),
+    time_ms,
+    json_or_null(error.as_deref())
+  );
+  Response { status, body }
+}
+
+fn route(request: &Request, config: &Config) -> Response {
+  let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+  let default_year = config.year.to_string();
+  let (year, day, part) = match segments.as_slice() {
+    [year, "day", day, "part", part] => (*year, *day, *part),
+    ["day", day, "part", part] => (default_year.as_str(), *day, *part),
+    _ => return error_response(404, "not found - try POST /{year}/day/{n}/part/{p}"),
+  };
+  if request.method != "POST" {
+    return error_response(405, "POST the puzzle input to this URL");
+  }
+  match (year.parse(), day.parse(), part.parse()) {
+    (Ok(year), Ok(day), Ok(part)) => solve(request, year, day, part, config.timeout),
+    _ => error_response(404, "the year, day and part should be numbers"),
+  }
+}
+
+fn handle_connection(stream: TcpStream, config: &Config) {
+  stream.set_read_timeout(Some(READ_TIMEOUT)).unwrap();
+  let response = match read_request(&stream, config.max_body_bytes) {
+    Ok(request) => route(&request, config),
+    Err(response) => response,
+  };
+  let head = format!(
+    "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+    response.status,
+    status_text(response.status),
+    response.body.len()
+  );
+  // if the client has already gone away there's nobody to tell about it
+  let mut stream = stream;
+  let _ = stream.write_all(head.as_bytes()).and_then(|_| stream.write_all(response.body.as_bytes()));
+}
+
+pub fn serve(config: Config) {
+  // only listen on localhost - this isn't meant to be exposed any more widely than that
+  let listener = TcpListener::bind(("127.0.0.1", config.port)).unwrap();
+  println!("listening on http://{}", listener.local_addr().unwrap());
+  let config = Arc::new(config);
+  for stream in listener.incoming() {
+    match stream {
+      Ok(stream) => {
+        let config = config.clone();
+        thread::spawn(move || handle_connection(stream, &config));
+      },
+      Err(error) => eprintln!("failed to accept connection: {}", error),
+    }
+  }
+}
@@ -1,25 +1,192 @@
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day17;
-pub mod day18;
-pub mod day19;
-pub mod day20;
-pub mod day21;
-pub mod day22;
-pub mod day23;
-pub mod day24;
-pub mod day25;
+pub mod picture;
+pub mod reader;
+pub mod year2023;
+
+use picture::Picture;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+// Each year's solutions live in a module of their own (year2023 and so on), with the days inside it. Every year
+// has a table of its days' solutions, taking the puzzle input as a string rather than reading it from the
+// `input` directory. This is what lets the various tools (difftest, minimize and so on) run any day
+// without needing to know anything about it.
+
+// how a day's input is laid out, which tells the tools which pieces of it can sensibly be removed or replaced
+#[derive(Clone, Copy, PartialEq)]
+pub enum InputShape {
+  // one record per line, each made up of whitespace-separated tokens
+  Lines,
+  // a rectangular grid of characters
+  Grid,
+  // blocks of lines separated by blank lines
+  Sections,
+  // a single line of comma-separated items
+  CommaSeparated,
+}
+
+// Values to use instead of the constants given in the puzzle text (the number of steps to take, the size of the
+// test area and so on), keyed by the name of the command line option that sets them. Mostly useful for checking
+// against the examples in the puzzle text, which often use smaller numbers than the real puzzle. Anything not
+// given falls back to the real puzzle's value.
+#[derive(Clone, Default)]
+pub struct Params(HashMap<String, String>);
+
+impl Params {
+  // Picks out `--name value` pairs for any of the day's parameters. Each value is checked as it's picked out, and
+  // then the day's check on the whole lot of them (as with a minimum and maximum that have to be the right way
+  // round), so that a bad value is an error here rather than a panic halfway through solving.
+  pub fn from_args(args: &[String], day: &Day) -> Result<Params, String> {
+    let mut values = HashMap::new();
+    for pair in args.windows(2) {
+      if let Some(param) = pair[0].strip_prefix("--").and_then(|name| day.params.iter().find(|param| param.name == name)) {
+        (param.check)(&pair[1]).map_err(|problem| format!("invalid value for --{}: {} ({})", param.name, pair[1], problem))?;
+        values.insert(param.name.to_owned(), pair[1].clone());
+      }
+    }
+    let params = Params(values);
+    if let Some(check) = day.check_params {
+      check(&params)?;
+    }
+    Ok(params)
+  }
+
+  // the value given for a parameter, or the default if there isn't one - `from_args` has already checked it
+  pub fn get<T: FromStr>(&self, name: &str, default: T) -> T {
+    match self.0.get(name) {
+      None => default,
+      Some(value) => value.parse().unwrap_or_else(|_| panic!("invalid value for --{}: {}", name, value)),
+    }
+  }
+
+  // the command line options that would give these same parameters, for passing on to a worker process
+  pub fn to_args(&self) -> Vec<String> {
+    self.0.iter().flat_map(|(name, value)| [format!("--{}", name), value.clone()]).collect()
+  }
+}
+
+// One of the parameters a day's solutions take: the name of its command line option, and a check that a value is one
+// the solutions can use, giving what's wrong with it if not. Mostly that's just that it parses as the type the
+// solutions read it as - written like `Param { name: "cycles", check: parses::<usize> }`.
+pub struct Param {
+  pub name: &'static str,
+  pub check: fn(&str) -> Result<(), String>,
+}
+
+pub fn parses<T: FromStr>(value: &str) -> Result<(), String> where T::Err: Display {
+  value.parse::<T>().map(|_| ()).map_err(|error| error.to_string())
+}
+
+pub type Solver = fn(&str, &Params) -> String;
+
+pub type ParamsCheck = fn(&Params) -> Result<(), String>;
+
+// Many of the solutions rely on properties of the real puzzle input that the puzzle text never promises (see day 8
+// for a good example). Where these are checked, a failure panics with a message starting with this, so that the
+// tools can tell a broken assumption apart from any other error.
+pub const ASSUMPTION_FAILED: &str = "assumption failed: ";
+
+pub fn assumption_failed(description: &str) -> ! {
+  panic!("{}{}", ASSUMPTION_FAILED, description)
+}
+
+// Arithmetic that could overflow on a big enough input, written as eg. `checked!(add, start, range)`. Normally
+// this is just the plain operator - which in a release build wraps round without saying anything - but when built
+// with the `checked-arithmetic` feature it panics instead, saying exactly which calculation overflowed and what
+// the values were.
+macro_rules! checked {
+  (add, $a:expr, $b:expr) => { $crate::solutions::checked!(@apply $a, $b, checked_add, +) };
+  (sub, $a:expr, $b:expr) => { $crate::solutions::checked!(@apply $a, $b, checked_sub, -) };
+  (mul, $a:expr, $b:expr) => { $crate::solutions::checked!(@apply $a, $b, checked_mul, *) };
+  (@apply $a:expr, $b:expr, $method:ident, $operator:tt) => {{
+    let (a, b) = ($a, $b);
+    if cfg!(feature = "checked-arithmetic") {
+      match a.$method(b) {
+        Some(result) => result,
+        None => $crate::solutions::overflowed(concat!(stringify!($a), " ", stringify!($operator), " ", stringify!($b)), &a, &b),
+      }
+    } else {
+      a $operator b
+    }
+  }};
+}
+pub(crate) use checked;
+
+pub const OVERFLOWED: &str = "arithmetic overflow: ";
+
+// the panic is reported as coming from wherever the `checked!` was, rather than from here
+#[track_caller]
+pub fn overflowed(expression: &str, a: &dyn Display, b: &dyn Display) -> ! {
+  panic!("{}{} (with {} and {})", OVERFLOWED, expression, a, b)
+}
+
+pub struct Day {
+  pub year: u16,
+  pub number: u8,
+  pub shape: InputShape,
+  // the parameters (see above) that this day's solutions use, and a check on them all together where one value
+  // limits another
+  pub params: &'static [Param],
+  pub check_params: Option<ParamsCheck>,
+  pub part_1: Solver,
+  // there's only one part on the last day
+  pub part_2: Option<Solver>,
+  // a picture of the solution, for the report - for the days where there's something worth seeing
+  pub render: Option<fn(&str) -> Picture>,
+}
+
+impl Day {
+  pub fn part(&self, part: u8) -> Option<Solver> {
+    match part {
+      1 => Some(self.part_1),
+      2 => self.part_2,
+      _ => None,
+    }
+  }
+}
+
+pub fn all_days(year: u16) -> Vec<Day> {
+  match year {
+    2023 => year2023::days(),
+    _ => vec![],
+  }
+}
+
+// prints the answers to every part of every day for the year, using the inputs in the input directory
+pub fn run(year: u16) {
+  match year {
+    2023 => year2023::run(),
+    _ => println!("no solutions for {} yet", year),
+  }
+}
+
+// where the input for a day is expected to be, if it's been put there by hand
+pub fn input_path(year: u16, day: u8) -> String {
+  format!("./input/{}/input{}.txt", year, day)
+}
+
+pub fn get_day(year: u16, number: u8) -> Option<Day> {
+  all_days(year).into_iter().find(|day| day.number == number)
+}
+
+#[cfg(all(test, feature = "year2023-day17"))]
+mod tests {
+  use super::*;
+
+  fn from_args(args: &[&str]) -> Result<Params, String> {
+    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    Params::from_args(&args, &get_day(2023, 17).unwrap())
+  }
+
+  #[test]
+  fn parameters_are_checked_one_by_one_and_together() {
+    assert_eq!(from_args(&["--part-1-max-distance", "20"]).unwrap().get("part-1-max-distance", 0), 20);
+    assert!(from_args(&["--part-1-max-distance", "-1"]).err().unwrap().starts_with("invalid value for --part-1-max-distance: -1"));
+    assert!(from_args(&["--part-1-min-distance", "0"]).is_err());
+    // the other end of the range comes from the default if it isn't given
+    assert!(from_args(&["--part-2-min-distance", "20"]).is_err());
+    assert!(from_args(&["--part-2-min-distance", "20", "--part-2-max-distance", "30"]).is_ok());
+    // anything that isn't one of the day's parameters is left alone
+    assert!(from_args(&["--cycles", "abc"]).is_ok());
+  }
+}
@@ -0,0 +1,83 @@
+// Pictures of what a solution found - the loop of pipes on day 10 and so on - for the report. They're drawn as
+// SVG, so that they can go straight into a page without needing any files alongside it.
+
+pub enum Picture {
+  // a grid of cells, each filled in with a colour (any CSS one, like "#ffcc00") or left empty
+  Grid(Vec<Vec<Option<&'static str>>>),
+  // a closed shape through the points given, which is shaded in, with each edge (from one point to the next)
+  // drawn in a colour of its own
+  Shape(Vec<((i64, i64), String)>),
+}
+
+// how big the pictures come out, in pixels - at least for the longer side
+const SIZE: usize = 560;
+
+fn grid_to_svg(cells: &[Vec<Option<&str>>]) -> String {
+  let height = cells.len();
+  let width = cells.iter().map(|row| row.len()).max().unwrap_or(0);
+  let mut svg = format!(
+    "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">",
+    SIZE * width / width.max(height).max(1), SIZE * height / width.max(height).max(1), width, height
+  );
+  // neighbouring cells of the same colour along a row are drawn as one rectangle, to keep the size down
+  for (row, row_cells) in cells.iter().enumerate() {
+    let mut col = 0;
+    while col < row_cells.len() {
+      let start = col;
+      while col < row_cells.len() && row_cells[col] == row_cells[start] {
+        col += 1;
+      }
+      if let Some(colour) = row_cells[start] {
+        svg.push_str(&format!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" fill=\"{}\"/>", start, row, col - start, colour));
+      }
+    }
+  }
+  svg.push_str("</svg>");
+  svg
+}
+
+// The edge colours come from the input (day 18's are in it), so anything that isn't a plain "#rrggbb" is drawn in
+// a fixed colour instead, rather than going into the SVG as it is.
+const FALLBACK_COLOUR: &str = "#808080";
+
+fn edge_colour(colour: &str) -> &str {
+  match colour.strip_prefix('#') {
+    Some(digits) if digits.len() == 6 && digits.chars().all(|digit| digit.is_ascii_hexdigit()) => colour,
+    _ => FALLBACK_COLOUR,
+  }
+}
+
+fn shape_to_svg(points: &[((i64, i64), String)]) -> String {
+  let min_x = points.iter().map(|((x, _), _)| *x).min().unwrap_or(0);
+  let max_x = points.iter().map(|((x, _), _)| *x).max().unwrap_or(0);
+  let min_y = points.iter().map(|((_, y), _)| *y).min().unwrap_or(0);
+  let max_y = points.iter().map(|((_, y), _)| *y).max().unwrap_or(0);
+  let (width, height) = ((max_x - min_x).max(1) as usize, (max_y - min_y).max(1) as usize);
+  // a margin of a fiftieth of the size, so that the edges aren't right at the sides
+  let margin = (width.max(height) / 50).max(1) as i64;
+  let mut svg = format!(
+    "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"{} {} {} {}\">",
+    SIZE * width / width.max(height), SIZE * height / width.max(height),
+    min_x - margin, min_y - margin, width as i64 + 2 * margin, height as i64 + 2 * margin
+  );
+  let corners: Vec<String> = points.iter().map(|((x, y), _)| format!("{},{}", x, y)).collect();
+  svg.push_str(&format!("<polygon points=\"{}\" fill=\"#d8d0c0\"/>", corners.join(" ")));
+  for (index, ((x1, y1), colour)) in points.iter().enumerate() {
+    let ((x2, y2), _) = points[(index + 1) % points.len()];
+    svg.push_str(&format!(
+      "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-width=\"3\" vector-effect=\"non-scaling-stroke\"/>",
+      x1, y1, x2, y2, edge_colour(colour)
+    ));
+  }
+  svg.push_str("</svg>");
+  svg
+}
+
+impl Picture {
+  pub fn to_svg(&self) -> String {
+    match self {
+      Picture::Grid(cells) => grid_to_svg(cells),
+      Picture::Shape(points) => shape_to_svg(points),
+    }
+  }
+}
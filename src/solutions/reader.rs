@@ -0,0 +1,30 @@
+use super::input_path;
+use std::fs;
+
+// Shared by every day's parser. Puzzle inputs don't always arrive exactly as the site serves them: saved on
+// Windows they have CRLF line endings, some editors put a byte order mark at the front, and copying and pasting
+// can leave spaces at the ends of lines or an extra blank line at the end. The parsers all start by normalizing
+// the text, so that they only ever have to deal with the tidy version.
+
+// the input file for a day, as it is on disk
+pub fn read(year: u16, day: u8) -> String {
+  fs::read_to_string(input_path(year, day)).unwrap()
+}
+
+// The input with a leading byte order mark removed, every line ending in a plain \n and with no whitespace at the
+// end of it, and no blank lines at the end. There's no \n after the last line either.
+pub fn normalize(contents: &str) -> String {
+  let contents = contents.strip_prefix('\u{feff}').unwrap_or(contents);
+  // trimming the end of each line takes care of any \r before the \n as well
+  let mut lines: Vec<&str> = contents.split('\n').map(|line| line.trim_end()).collect();
+  while lines.last().is_some_and(|line| line.is_empty()) {
+    lines.pop();
+  }
+  lines.join("\n")
+}
+
+// The blocks of lines separated by blank lines, in (normalized) input - several blank lines in a row count as just
+// one separator.
+pub fn sections(contents: &str) -> Vec<&str> {
+  contents.split("\n\n").map(|section| section.trim_matches('\n')).filter(|section| !section.is_empty()).collect()
+}
@@ -0,0 +1,428 @@
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+use std::fs;
+use std::str::FromStr;
+use crate::solutions::reader;
+use crate::trace::{self, Level};
+
+fn parse_input(contents: &str) -> Vec<String> {
+  let contents: &str = &reader::normalize(contents);
+  contents.lines().map(|n| n.to_string()).collect()
+}
+
+fn read_file() -> Vec<String> {
+  parse_input(&reader::read(2023, 1))
+}
+
+fn solve_part_1(v: Vec<String>, rule: Rule) -> i64 {
+  // just the digits themselves count in part 1
+  total(&calibrate(&v, &Scanner::new(&NumberWords(vec![])), rule))
+}
+
+pub fn part_1() -> i64 {
+    let nums = read_file();
+    solve_part_1(nums, Rule::FirstAndLast)
+}
+
+pub fn part_1_from_input(input: &str, rule: Rule) -> i64 {
+    let nums = parse_input(input);
+    solve_part_1(nums, rule)
+}
+
+// The words that count as digits in part 2, and the value each one stands for. Other than the puzzle's own
+// English, there are French and German sets built in, and any others can be loaded from a file of `word=value`
+// lines (blank lines and lines starting with # are ignored), so extra words like "zero" or "first" can be added.
+#[derive(Default)]
+pub struct NumberWords(Vec<(String, i32)>);
+
+// which dictionary part 2 uses - the name of a built-in one, or the path of a file. This is a parameter so that
+// calibration documents in other languages can be read too.
+pub const NUMBER_WORDS: &str = "english";
+
+const ENGLISH: &[(&str, i32)] = &[
+  ("one", 1), ("two", 2), ("three", 3), ("four", 4), ("five", 5), ("six", 6), ("seven", 7), ("eight", 8), ("nine", 9),
+];
+const FRENCH: &[(&str, i32)] = &[
+  ("un", 1), ("deux", 2), ("trois", 3), ("quatre", 4), ("cinq", 5), ("six", 6), ("sept", 7), ("huit", 8), ("neuf", 9),
+];
+const GERMAN: &[(&str, i32)] = &[
+  ("eins", 1), ("zwei", 2), ("drei", 3), ("vier", 4), ("fünf", 5), ("sechs", 6), ("sieben", 7), ("acht", 8), ("neun", 9),
+];
+
+impl NumberWords {
+  fn built_in(words: &[(&str, i32)]) -> NumberWords {
+    NumberWords(words.iter().map(|(word, value)| (word.to_string(), *value)).collect())
+  }
+
+  pub fn parse(contents: &str) -> Result<NumberWords, String> {
+    let mut words = vec![];
+    for (index, line) in contents.lines().enumerate() {
+      let line = line.trim();
+      if line.is_empty() || line.starts_with('#') {
+        continue;
+      }
+      let (word, value) = line.split_once('=').ok_or(format!("line {}: expected word=value, got {}", index + 1, line))?;
+      let (word, value) = (word.trim(), value.trim());
+      if word.is_empty() {
+        return Err(format!("line {}: no word before the =", index + 1));
+      }
+      match value.parse() {
+        Ok(value) if value >= 0 => words.push((word.to_owned(), value)),
+        _ => return Err(format!("line {}: the value for {} should be a whole number, not {}", index + 1, word, value)),
+      }
+    }
+    Ok(NumberWords(words))
+  }
+
+  // a built-in dictionary by name, or else one read from the file at that path
+  pub fn load(name: &str) -> Result<NumberWords, String> {
+    match name {
+      "english" => Ok(NumberWords::built_in(ENGLISH)),
+      "french" => Ok(NumberWords::built_in(FRENCH)),
+      "german" => Ok(NumberWords::built_in(GERMAN)),
+      path => {
+        let contents = fs::read_to_string(path).map_err(|error| format!("couldn't read {}: {}", path, error))?;
+        NumberWords::parse(&contents).map_err(|message| format!("{}: {}", path, message))
+      },
+    }
+  }
+
+}
+
+// the check on the `number-words` parameter: that it names a dictionary that can be loaded
+pub fn check_number_words(name: &str) -> Result<(), String> {
+  NumberWords::load(name).map(|_| ())
+}
+
+// a digit or number word found in a line: where it is (as a range of bytes) and the value it stands for
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Token {
+  pub start: usize,
+  pub end: usize,
+  pub value: i32,
+}
+
+// An Aho-Corasick automaton for the digits and all the words of a dictionary at once, so that a line can be
+// scanned for every one of them in a single pass. It's built as a complete table of transitions - one for each
+// state and byte - so scanning never has to follow failure links, just look up the next state for each byte.
+pub struct Scanner {
+  transitions: Vec<[usize; 256]>,
+  // the (length, value) of every pattern that ends on reaching each state
+  matches: Vec<Vec<(usize, i32)>>,
+}
+
+impl Scanner {
+  pub fn new(words: &NumberWords) -> Scanner {
+    let digits: Vec<(String, i32)> = (0..10).map(|digit| (digit.to_string(), digit)).collect();
+    let mut transitions = vec![[0; 256]];
+    let mut matches = vec![vec![]];
+    // first the trie of all the patterns, where 0 means there's no transition yet (the root can't be re-entered)
+    for (pattern, value) in digits.iter().chain(words.0.iter()) {
+      let mut state = 0;
+      for &byte in pattern.as_bytes() {
+        if transitions[state][byte as usize] == 0 {
+          transitions.push([0; 256]);
+          matches.push(vec![]);
+          transitions[state][byte as usize] = transitions.len() - 1;
+        }
+        state = transitions[state][byte as usize];
+      }
+      matches[state].push((pattern.len(), *value));
+    }
+    // Then breadth first from the root, fill in the missing transitions from each state with those of its failure
+    // state (the longest proper suffix of it that's also in the trie), which is always nearer the root so already
+    // done. A state also matches everything its failure state does.
+    let mut failure = vec![0; transitions.len()];
+    let mut queue: VecDeque<usize> = transitions[0].iter().copied().filter(|&state| state != 0).collect();
+    while let Some(state) = queue.pop_front() {
+      let fallbacks = transitions[failure[state]];
+      for (byte, next) in transitions[state].iter_mut().enumerate() {
+        let fallback = fallbacks[byte];
+        if *next == 0 {
+          *next = fallback;
+        } else {
+          failure[*next] = fallback;
+          let inherited = matches[fallback].clone();
+          matches[*next].extend(inherited);
+          queue.push_back(*next);
+        }
+      }
+    }
+    Scanner { transitions, matches }
+  }
+
+  // Every digit and word in the line, in order of where they start - where several start at the same place (say
+  // "seven" and "seventeen"), the longest comes first. Words can overlap, so "eightwo" gives both an eight and a
+  // two. Since the patterns are whole UTF-8 strings, matching bytes can't find one starting partway through a
+  // character.
+  pub fn tokens(&self, line: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut state = 0;
+    for (index, &byte) in line.as_bytes().iter().enumerate() {
+      state = self.transitions[state][byte as usize];
+      for &(length, value) in self.matches[state].iter() {
+        tokens.push(Token { start: index + 1 - length, end: index + 1, value });
+      }
+    }
+    tokens.sort_by_key(|token| (token.start, Reverse(token.end)));
+    tokens
+  }
+}
+
+// How a line's calibration value is worked out from the values found in it. The puzzle's own rule is the first
+// value followed by the last; the others are for calibration documents that do things differently.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Rule {
+  FirstAndLast,
+  // the first (or last) so many values, one after another
+  First(usize),
+  Last(usize),
+  // all the values added up
+  Sum,
+}
+
+impl FromStr for Rule {
+  type Err = String;
+
+  // "first-last", "first-N", "last-N" or "sum"
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let count = |n: &str| n.parse().ok().filter(|&n| n > 0);
+    match s.split_once('-') {
+      _ if s == "first-last" => Ok(Rule::FirstAndLast),
+      _ if s == "sum" => Ok(Rule::Sum),
+      Some(("first", n)) if count(n).is_some() => Ok(Rule::First(count(n).unwrap())),
+      Some(("last", n)) if count(n).is_some() => Ok(Rule::Last(count(n).unwrap())),
+      _ => Err(format!("unknown calibration rule: {} (expected first-last, first-N, last-N or sum)", s)),
+    }
+  }
+}
+
+// everything found in one line of the input, and the calibration value worked out from it
+pub struct LineCalibration {
+  // counting from 1, as an editor would
+  pub line_number: usize,
+  pub tokens: Vec<Token>,
+  // what's wrong with the line, if there's no value for it
+  pub value: Result<i64, String>,
+}
+
+// The values in the line, one for each place a token starts - where several start at the same place (as with
+// "seven" and "seventeen") it's the longest that's meant, which `tokens` puts first.
+fn values(tokens: &[Token]) -> Vec<i64> {
+  let mut values = vec![];
+  for (index, token) in tokens.iter().enumerate() {
+    if index == 0 || tokens[index - 1].start != token.start {
+      values.push(token.value as i64);
+    }
+  }
+  values
+}
+
+// values written one after the other, as in "the first value followed by the last" - which is just
+// 10 * first + last when they're both digits, but also works for words with bigger values, like "ten"
+fn concatenate(values: &[i64]) -> Result<i64, String> {
+  let digits: String = values.iter().map(|value| value.to_string()).collect();
+  digits.parse().map_err(|_| format!("{} is too big for a calibration value", digits))
+}
+
+fn line_value(tokens: &[Token], rule: Rule) -> Result<i64, String> {
+  let values = values(tokens);
+  if values.is_empty() {
+    return Err(String::from("no digits"));
+  }
+  match rule {
+    Rule::FirstAndLast => concatenate(&[values[0], values[values.len() - 1]]),
+    Rule::First(count) | Rule::Last(count) if values.len() < count => {
+      Err(format!("the rule needs {} values, but there are only {}", count, values.len()))
+    },
+    Rule::First(count) => concatenate(&values[..count]),
+    Rule::Last(count) => concatenate(&values[values.len() - count..]),
+    Rule::Sum => Ok(values.iter().sum()),
+  }
+}
+
+// every line's tokens and value - a line without a value doesn't stop the others being worked out
+pub fn calibrate(lines: &[String], scanner: &Scanner, rule: Rule) -> Vec<LineCalibration> {
+  lines.iter().enumerate().map(|(index, line)| {
+    let tokens = scanner.tokens(line);
+    let calibration = LineCalibration { line_number: index + 1, value: line_value(&tokens, rule), tokens };
+    match &calibration.value {
+      Ok(value) => trace::emit(Level::Detail, "line", &[("line", &calibration.line_number), ("value", value)], || {
+        let found: Vec<String> = calibration.tokens.iter().map(|token| {
+          format!("{} at {}..{}", &line[token.start..token.end], token.start, token.end)
+        }).collect();
+        format!("line {} has {}, giving {}", calibration.line_number, found.join(", "), value)
+      }),
+      Err(problem) => trace::emit(Level::Summary, "bad line", &[("line", &calibration.line_number)], || {
+        format!("line {} has no calibration value: {}", calibration.line_number, problem)
+      }),
+    }
+    calibration
+  }).collect()
+}
+
+// The sum of all the calibration values. The puzzle promises every line has one, but if any don't they're left
+// out with a warning rather than there being no answer at all - `calibrate` has already pointed out which lines
+// they are under --explain, and the `calibration` command lists them too.
+fn total(calibrations: &[LineCalibration]) -> i64 {
+  let skipped = calibrations.iter().filter(|calibration| calibration.value.is_err()).count();
+  if skipped > 0 {
+    let plural = if skipped == 1 { "" } else { "s" };
+    trace::emit(Level::Summary, "skipped lines", &[("count", &skipped)], || {
+      format!("left {} line{} without a calibration value out of the total", skipped, plural)
+    });
+    trace::warn(format!("left {} line{} without a calibration value out of the total (see --explain)", skipped, plural));
+  }
+  calibrations.iter().filter_map(|calibration| calibration.value.as_ref().ok()).sum()
+}
+
+fn solve_part_2(v: Vec<String>, words: &NumberWords, rule: Rule) -> i64 {
+  total(&calibrate(&v, &Scanner::new(words), rule))
+}
+
+pub fn part_2() -> i64 {
+    let nums = read_file();
+    solve_part_2(nums, &NumberWords::load(NUMBER_WORDS).unwrap(), Rule::FirstAndLast)
+}
+
+pub fn part_2_from_input(input: &str, words: &NumberWords, rule: Rule) -> i64 {
+    let nums = parse_input(input);
+    solve_part_2(nums, words, rule)
+}
+
+// Entry point for the `calibration` command: each line of the input with the digits and words found in it and
+// the value they give (or why there isn't one), and then the total.
+pub fn calibration_report(input: &str, words: &NumberWords, rule: Rule) -> String {
+  let lines = parse_input(input);
+  let calibrations = calibrate(&lines, &Scanner::new(words), rule);
+  let mut report: Vec<String> = lines.iter().zip(calibrations.iter()).map(|(line, calibration)| {
+    let found: Vec<String> = calibration.tokens.iter().map(|token| {
+      format!("{} ({}..{})", &line[token.start..token.end], token.start, token.end)
+    }).collect();
+    let found = if found.is_empty() { String::from("nothing") } else { found.join(", ") };
+    let value = match &calibration.value {
+      Ok(value) => format!("value {}", value),
+      Err(problem) => format!("no value: {}", problem),
+    };
+    format!("line {}: {} - found {} - {}", calibration.line_number, line, found, value)
+  }).collect();
+  let valid = calibrations.iter().filter(|calibration| calibration.value.is_ok()).count();
+  let sum: i64 = calibrations.iter().filter_map(|calibration| calibration.value.as_ref().ok()).sum();
+  report.push(format!("total {} from {} of {} lines", sum, valid, calibrations.len()));
+  report.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // the example from part 2 of the puzzle
+  const EXAMPLE: &str = "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen";
+
+  fn values_found(words: &NumberWords, line: &str) -> Vec<i64> {
+    values(&Scanner::new(words).tokens(line))
+  }
+
+  // The way lines were scanned before there was a `Scanner`: at each character in turn, a digit, or else the longest
+  // word starting there.
+  fn scan_each_position(words: &NumberWords, line: &str) -> Vec<i64> {
+    line.char_indices().filter_map(|(index, _)| {
+      let rest = &line[index..];
+      match rest.chars().next().and_then(|char| char.to_digit(10)) {
+        Some(digit) => Some(digit as i64),
+        None => words.0.iter().filter(|(word, _)| rest.starts_with(word.as_str())).max_by_key(|(word, _)| word.len()).map(|(_, value)| *value as i64),
+      }
+    }).collect()
+  }
+
+  #[test]
+  fn overlapping_words_are_all_found() {
+    let english = NumberWords::load("english").unwrap();
+    assert_eq!(values_found(&english, "eightwo"), [8, 2]);
+    assert_eq!(values_found(&english, "oneight"), [1, 8]);
+    assert_eq!(values_found(&english, "twone"), [2, 1]);
+    assert_eq!(values_found(&english, "sevenine"), [7, 9]);
+  }
+
+  #[test]
+  fn words_inside_other_words_are_found_too() {
+    let words = NumberWords::parse("seven=7\nseventeen=17\nteen=10").unwrap();
+    let tokens = Scanner::new(&words).tokens("seventeen");
+    assert_eq!(tokens, [
+      Token { start: 0, end: 9, value: 17 },
+      Token { start: 0, end: 5, value: 7 },
+      Token { start: 5, end: 9, value: 10 },
+    ]);
+    // where two start at the same place, only the longer one counts
+    assert_eq!(values(&tokens), [17, 10]);
+  }
+
+  #[test]
+  fn words_with_multi_byte_characters_are_found() {
+    let german = NumberWords::load("german").unwrap();
+    let tokens = Scanner::new(&german).tokens("xfünfy3");
+    assert_eq!(tokens, [Token { start: 1, end: 6, value: 5 }, Token { start: 7, end: 8, value: 3 }]);
+    assert_eq!(values_found(&german, "fünfünf"), [5, 5]);
+    // a word sharing its start with another only counts once it's complete
+    let french = NumberWords::parse("trois=3\ntreize=13").unwrap();
+    assert_eq!(values_found(&french, "treizetrois"), [13, 3]);
+    assert_eq!(values_found(&french, "tretroitre"), Vec::<i64>::new());
+  }
+
+  #[test]
+  fn scanning_matches_trying_each_position() {
+    let lines = EXAMPLE.lines().chain(["fünfsechs9eins", "oneightwone", "no digits at all"]);
+    for (name, line) in ["english", "french", "german"].iter().flat_map(|name| lines.clone().map(move |line| (name, line))) {
+      let words = NumberWords::load(name).unwrap();
+      assert_eq!(values_found(&words, line), scan_each_position(&words, line), "{} with {}", line, name);
+    }
+    assert_eq!(part_2_from_input(EXAMPLE, &NumberWords::load("english").unwrap(), Rule::FirstAndLast), 281);
+  }
+
+  #[test]
+  fn rules_are_parsed() {
+    assert_eq!("first-last".parse::<Rule>(), Ok(Rule::FirstAndLast));
+    assert_eq!("first-3".parse::<Rule>(), Ok(Rule::First(3)));
+    assert_eq!("last-2".parse::<Rule>(), Ok(Rule::Last(2)));
+    assert_eq!("sum".parse::<Rule>(), Ok(Rule::Sum));
+    for bad in ["first-0", "last-", "first-two", "middle-2", "first", ""] {
+      assert!(bad.parse::<Rule>().is_err(), "{}", bad);
+    }
+  }
+
+  #[test]
+  fn each_rule_gives_its_own_values() {
+    let english = NumberWords::load("english").unwrap();
+    let lines = parse_input(EXAMPLE);
+    let values_with = |rule| -> Vec<Result<i64, String>> {
+      calibrate(&lines, &Scanner::new(&english), rule).into_iter().map(|calibration| calibration.value).collect()
+    };
+    assert_eq!(values_with(Rule::First(2)), [Ok(21), Ok(82), Ok(12), Ok(21), Ok(49), Ok(18), Ok(76)]);
+    assert_eq!(values_with(Rule::Last(2)), [Ok(19), Ok(23), Ok(23), Ok(34), Ok(72), Ok(34), Ok(76)]);
+    assert_eq!(values_with(Rule::Sum), [Ok(12), Ok(13), Ok(6), Ok(10), Ok(30), Ok(18), Ok(13)]);
+    // a single value is both the first and the last
+    assert_eq!(values_with(Rule::First(1))[6], Ok(7));
+    assert_eq!(values_with(Rule::Last(1))[6], Ok(6));
+  }
+
+  #[test]
+  fn lines_without_enough_values_are_skipped() {
+    let english = NumberWords::load("english").unwrap();
+    let lines = parse_input("abc123\nsevenine\nno digits\n4");
+    let calibrations = calibrate(&lines, &Scanner::new(&english), Rule::Last(3));
+    let values: Vec<Result<i64, String>> = calibrations.iter().map(|calibration| calibration.value.clone()).collect();
+    assert_eq!(values, [
+      Ok(123),
+      Err(String::from("the rule needs 3 values, but there are only 2")),
+      Err(String::from("no digits")),
+      Err(String::from("the rule needs 3 values, but there are only 1")),
+    ]);
+    assert_eq!(calibrations.iter().map(|calibration| calibration.line_number).collect::<Vec<_>>(), [1, 2, 3, 4]);
+
+    // the total leaves them out, with a warning - which other tests could be adding to at the same time
+    assert_eq!(total(&calibrations), 123);
+    assert!(trace::take_warnings().contains(&String::from("left 3 lines without a calibration value out of the total (see --explain)")));
+    let example = parse_input(EXAMPLE);
+    assert_eq!(total(&calibrate(&example, &Scanner::new(&english), Rule::First(3))), 219 + 823 + 123 + 213 + 498 + 182);
+  }
+}
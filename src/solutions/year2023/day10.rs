@@ -1,5 +1,5 @@
-use std::fs::File;
-use std::io::prelude::*;
+use crate::solutions::picture::Picture;
+use crate::solutions::reader;
 
 #[derive(Clone, Copy)]
 enum Tile {
@@ -30,11 +30,8 @@ enum Direction {
   West,
 }
 
-fn read_file() -> Grid {
-  let mut file = File::open("./input/input10.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
-
+fn parse_input(contents: &str) -> Grid {
+  let contents: &str = &reader::normalize(contents);
   let mut tiles = vec![];
   for row in contents.lines() {
     let mut pipes = vec![];
@@ -58,6 +55,10 @@ fn read_file() -> Grid {
   Grid { tiles }
 }
 
+fn read_file() -> Grid {
+  parse_input(&reader::read(2023, 10))
+}
+
 fn follow_path(grid: &Grid, row: usize, column: usize, incoming_direction: &Option<Direction>) -> Direction {
   match grid.get_tile(row, column) {
     Tile::PipeVertical => {
@@ -187,6 +188,11 @@ pub fn part_1() -> u32 {
   solve_part_1(&grid)
 }
 
+pub fn part_1_from_input(input: &str) -> u32 {
+  let grid = parse_input(input);
+  solve_part_1(&grid)
+}
+
 // basically a repeat of the part 1 solution, but compiling a list of all the points traversed,
 // because we'll need all these for part 2
 fn get_loop_path(grid: &Grid) -> Vec<(usize, usize)> {
@@ -280,7 +286,7 @@ fn get_start_type(grid: &Grid) -> Tile {
   panic!("no valid pipe found for start tile!");
 }
 
-fn solve_part_2(grid: &Grid) -> u32 {
+fn find_inside_tiles(grid: &Grid, loop_tiles: &[(usize, usize)]) -> Vec<(usize, usize)> {
   // the approach is as follows. Go down each row, counting the number of times
   // we cross a pipe tile that's in the loop. The idea is to keep track of how many
   // times we've crossed the loop - where this is odd, we must be inside the loop, and
@@ -296,8 +302,7 @@ fn solve_part_2(grid: &Grid) -> u32 {
   // of path we just followed could have been shiften just one unit and we wouldn't have seen it at atll).
   // Where if we enter at a North/East tile and exit at a South/West one, or vice versa, we have moved from
   // the South side to the North side (or vice versa) of the local section of path, so have crossed it.
-  let loop_tiles = get_loop_path(grid);
-  let mut inside_tiles = 0;
+  let mut inside_tiles = vec![];
   for row in 0..grid.tiles.len() {
     let mut is_inside = false;
     let mut join_direction = None;
@@ -330,14 +335,38 @@ fn solve_part_2(grid: &Grid) -> u32 {
           _ => (),
         }
       } else if is_inside {
-        inside_tiles += 1;
+        inside_tiles.push((row, col));
       }
     }
   }
   inside_tiles
 }
 
+fn solve_part_2(grid: &Grid) -> u32 {
+  let loop_tiles = get_loop_path(grid);
+  find_inside_tiles(grid, &loop_tiles).len() as u32
+}
+
 pub fn part_2() -> u32 {
   let grid = read_file();
   solve_part_2(&grid)
 }
+
+pub fn part_2_from_input(input: &str) -> u32 {
+  let grid = parse_input(input);
+  solve_part_2(&grid)
+}
+
+// for the report: the loop (in blue), and the tiles inside it (in orange)
+pub fn render_from_input(input: &str) -> Picture {
+  let grid = parse_input(input);
+  let loop_tiles = get_loop_path(&grid);
+  let mut cells: Vec<Vec<Option<&str>>> = grid.tiles.iter().map(|row| vec![None; row.len()]).collect();
+  for &(row, col) in loop_tiles.iter() {
+    cells[row][col] = Some("#3366cc");
+  }
+  for (row, col) in find_inside_tiles(&grid, &loop_tiles) {
+    cells[row][col] = Some("#ff9933");
+  }
+  Picture::Grid(cells)
+}
@@ -1,5 +1,4 @@
-use std::fs::File;
-use std::io::prelude::*;
+use crate::solutions::reader;
 
 // collect relevant info about puzzle - some of this is redundant but it's
 // all at least important for part 1 and we can easily collect it all directly
@@ -11,11 +10,8 @@ struct PuzzleData {
   galaxies: Vec<(usize, usize)>,
 }
 
-fn read_file() -> PuzzleData {
-  let mut file = File::open("./input/input11.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
-
+fn parse_input(contents: &str) -> PuzzleData {
+  let contents: &str = &reader::normalize(contents);
   let mut non_empty_cols = vec![];
   let mut empty_rows = vec![];
   let mut galaxies = vec![];
@@ -40,6 +36,10 @@ fn read_file() -> PuzzleData {
   PuzzleData { empty_rows, empty_cols, galaxies }
 }
 
+fn read_file() -> PuzzleData {
+  parse_input(&reader::read(2023, 11))
+}
+
 // common utility, used for both parts 1 and 2 since they differ in only one "small" detail
 
 fn get_total(data: &PuzzleData, expansion_factor: u64) -> u64 {
@@ -75,11 +75,25 @@ pub fn part_1() -> u64 {
   solve_part_1(&data)
 }
 
-fn solve_part_2(data: &PuzzleData) -> u64 {
-  get_total(data, 1000000)
+pub fn part_1_from_input(input: &str) -> u64 {
+  let data = parse_input(input);
+  solve_part_1(&data)
+}
+
+// the factor each empty row and column expands by in part 2. This is a parameter so that the smaller factors
+// in the puzzle's examples can be checked.
+pub const EXPANSION_FACTOR: u64 = 1000000;
+
+fn solve_part_2(data: &PuzzleData, expansion_factor: u64) -> u64 {
+  get_total(data, expansion_factor)
 }
 
 pub fn part_2() -> u64 {
   let data = read_file();
-  solve_part_2(&data)
+  solve_part_2(&data, EXPANSION_FACTOR)
+}
+
+pub fn part_2_from_input(input: &str, expansion_factor: u64) -> u64 {
+  let data = parse_input(input);
+  solve_part_2(&data, expansion_factor)
 }
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::prelude::*;
+use crate::profile;
+use crate::solutions::checked;
+use crate::solutions::reader;
 
 #[derive(Clone)]
 enum SpringCondition {
@@ -31,14 +32,15 @@ fn read_line(line: &str) -> Row {
   Row { springs, groups }
 }
 
-fn read_file() -> Vec<Row> {
-  let mut file = File::open("./input/input12.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
-
+fn parse_input(contents: &str) -> Vec<Row> {
+  let contents: &str = &reader::normalize(contents);
   contents.lines().map(read_line).collect()
 }
 
+fn read_file() -> Vec<Row> {
+  parse_input(&reader::read(2023, 12))
+}
+
 // I first tried this puzzle with a brute-force approach - essentially trying all possible ways of filling in
 // the unknown springs (only short-circuiting in really obvious cases), and counting how many fit the pattern.
 // This actually worked fine for part 1 but runs for an age for part 2 - so a new approach was needed.
@@ -111,6 +113,7 @@ fn rle_encode(springs: &Vec<SpringCondition>) -> Vec<Vec<u64>> {
 fn count_fits(rle_piece: &Vec<u64>, groups: &Vec<u64>, previous_results: &mut HashMap<(Vec<u64>, Vec<u64>), u64>) -> u64 {
   // use memoised result, if it exists
   if let Some(&ans) = previous_results.get(&(rle_piece.clone(), groups.clone())) {
+    profile::count("memo hits");
     return ans;
   }
 
@@ -119,12 +122,14 @@ fn count_fits(rle_piece: &Vec<u64>, groups: &Vec<u64>, previous_results: &mut Ha
   // that we don't have - but if not, we've succeeded.
   if groups.is_empty() {
     let solution = if rle_piece.len() > 1 { 0 } else { 1 };
+    profile::count("states memoised");
     previous_results.insert((rle_piece.clone(), groups.clone()), solution);
     return solution;
   }
 
   // if we still have groups left but are out of places to fit them, there must be no solution
   if rle_piece.is_empty() {
+    profile::count("states memoised");
     previous_results.insert((rle_piece.clone(), groups.clone()), 0);
     return 0;
   }
@@ -137,7 +142,7 @@ fn count_fits(rle_piece: &Vec<u64>, groups: &Vec<u64>, previous_results: &mut Ha
   // group for now - if some positions leave not enough space to fit later groups in what remains, we discover that
   // in one of the recursive calls below.
   let piece_length: u64 = rle_piece.iter().sum();
-  let mut total_fits = 0;
+  let mut total_fits: u64 = 0;
   let mut possible_starts = vec![];
 
   // First note that when we're in the second "piece" of the RLE, that corresponds to a known spring - we can start
@@ -216,10 +221,11 @@ fn count_fits(rle_piece: &Vec<u64>, groups: &Vec<u64>, previous_results: &mut Ha
         remaining_rle.push(remaining_group);
       }
     }
-    total_fits += count_fits(&remaining_rle, &rest, previous_results);
+    total_fits = checked!(add, total_fits, count_fits(&remaining_rle, &rest, previous_results));
   }
 
   // memoise result
+  profile::count("states memoised");
   previous_results.insert((rle_piece.clone(), groups.clone()), total_fits);
   total_fits
 }
@@ -234,7 +240,7 @@ fn solve(rle_pieces: &Vec<Vec<u64>>, groups: &Vec<u64>, previous_results: &mut H
   }
 
   // start counting the number of solutions
-  let mut num_solutions = 0;
+  let mut num_solutions: u64 = 0;
 
   // pull out the next piece to try to fit groups into - and keep the rest for later
   let (next_piece, rest) = rle_pieces.split_at(1);
@@ -264,7 +270,8 @@ fn solve(rle_pieces: &Vec<Vec<u64>>, groups: &Vec<u64>, previous_results: &mut H
     let number_of_fits = count_fits(next_piece, &groups_to_fit, previous_results);
     // don't waste time recursing if we already know there's no solutions!
     if number_of_fits > 0 {
-      num_solutions += number_of_fits * solve(&rest, &remaining_groups, previous_results);
+      let ways_to_fit_the_rest = solve(&rest, &remaining_groups, previous_results);
+      num_solutions = checked!(add, num_solutions, checked!(mul, number_of_fits, ways_to_fit_the_rest));
     }
   }
 
@@ -276,7 +283,7 @@ fn solve(rle_pieces: &Vec<Vec<u64>>, groups: &Vec<u64>, previous_results: &mut H
   // Luckily we can easily tell this from our RLE representation - it happens when the first piece if all ?'s, which
   // is when the RLE has only a single element.
   if next_piece.len() == 1 {
-    num_solutions += solve(&rest, groups, previous_results);
+    num_solutions = checked!(add, num_solutions, solve(&rest, groups, previous_results));
   }
 
   num_solutions
@@ -289,7 +296,7 @@ fn get_number_of_combinations(row: &Row, previous_results: &mut HashMap<(Vec<u64
 
 fn solve_part_1(rows: &Vec<Row>) -> u64 {
   let mut fit_results = HashMap::new();
-  rows.iter().map(|row| get_number_of_combinations(row, &mut fit_results)).sum()
+  rows.iter().fold(0u64, |total, row| checked!(add, total, get_number_of_combinations(row, &mut fit_results)))
 }
 
 pub fn part_1() -> u64 {
@@ -297,16 +304,68 @@ pub fn part_1() -> u64 {
   solve_part_1(&rows)
 }
 
+pub fn part_1_from_input(input: &str) -> u64 {
+  let rows = parse_input(input);
+  solve_part_1(&rows)
+}
+
+// a resurrected version of the original brute-force approach mentioned at the top: try every possible way of
+// filling in the unknown springs and count the ones whose damaged groups match. Hopeless for part 2, but it's
+// easy to convince yourself it's correct, so it's kept as a reference for the RLE version on small rows.
+fn count_fits_brute_force(row: &Row) -> u64 {
+  let unknown_indices: Vec<usize> = row.springs.iter().enumerate().filter_map(|(index, spring)| match spring {
+    SpringCondition::Unknown => Some(index),
+    _ => None,
+  }).collect();
+
+  let known_damaged: Vec<bool> = row.springs.iter().map(|spring| matches!(spring, SpringCondition::Damaged)).collect();
+
+  let mut total = 0;
+  for filling in 0..(1u64 << unknown_indices.len()) {
+    let mut damaged = known_damaged.clone();
+    for (bit, &index) in unknown_indices.iter().enumerate() {
+      damaged[index] = filling & (1 << bit) != 0;
+    }
+
+    let mut groups = vec![];
+    let mut current_group = 0;
+    for is_damaged in damaged {
+      if is_damaged {
+        current_group += 1;
+      } else if current_group > 0 {
+        groups.push(current_group);
+        current_group = 0;
+      }
+    }
+    if current_group > 0 {
+      groups.push(current_group);
+    }
+
+    if groups == row.groups {
+      total += 1;
+    }
+  }
+  total
+}
+
+pub fn part_1_brute_force_from_input(input: &str) -> u64 {
+  let rows = parse_input(input);
+  rows.iter().map(count_fits_brute_force).sum()
+}
+
+// how many copies of each row there are after unfolding in part 2
+pub const UNFOLD_COPIES: usize = 5;
+
 // unfolds the row to transform from part 1 to part 2
-fn unfold(row: &Row) -> Row {
+fn unfold(row: &Row, copies: usize) -> Row {
   let Row { springs, groups } = row;
   let mut unfolded_springs = vec![];
   let mut unfolded_groups = vec![];
 
-  for i in 0..5 {
+  for i in 0..copies {
     unfolded_springs.append(&mut springs.clone());
     unfolded_groups.append(&mut groups.clone());
-    if i < 4 {
+    if i < copies - 1 {
       unfolded_springs.push(SpringCondition::Unknown);
     }
   }
@@ -314,12 +373,17 @@ fn unfold(row: &Row) -> Row {
   Row { springs: unfolded_springs, groups: unfolded_groups }
 }
 
-fn solve_part_2(rows: &Vec<Row>) -> u64 {
+fn solve_part_2(rows: &Vec<Row>, unfold_copies: usize) -> u64 {
   let mut fit_results = HashMap::new();
-  rows.iter().map(|row| get_number_of_combinations(&unfold(row), &mut fit_results)).sum()
+  rows.iter().fold(0u64, |total, row| checked!(add, total, get_number_of_combinations(&unfold(row, unfold_copies), &mut fit_results)))
 }
 
 pub fn part_2() -> u64 {
   let rows = read_file();
-  solve_part_2(&rows)
-}
\ No newline at end of file
+  solve_part_2(&rows, UNFOLD_COPIES)
+}
+
+pub fn part_2_from_input(input: &str, unfold_copies: usize) -> u64 {
+  let rows = parse_input(input);
+  solve_part_2(&rows, unfold_copies)
+}
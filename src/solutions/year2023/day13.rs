@@ -1,6 +1,5 @@
-use std::fs::File;
-use std::io::prelude::*;
 use std::cmp;
+use crate::solutions::reader;
 
 #[derive(PartialEq, Clone, Copy)]
 enum Space {
@@ -36,25 +35,13 @@ fn read_line(line: &str) -> Vec<Space> {
   }).collect()
 }
 
-fn read_file() -> Vec<Pattern> {
-  let mut file = File::open("./input/input13.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
-
-  let mut patterns = vec![];
-  let mut current_grid = vec![];
-
-  for line in contents.lines() {
-    if line.is_empty() {
-      patterns.push(Pattern { grid: current_grid });
-      current_grid = vec![];
-    } else {
-      current_grid.push(read_line(line));
-    }
-  }
-  patterns.push(Pattern { grid: current_grid });
+fn parse_input(contents: &str) -> Vec<Pattern> {
+  let contents: &str = &reader::normalize(contents);
+  reader::sections(contents).into_iter().map(|section| Pattern { grid: section.lines().map(read_line).collect() }).collect()
+}
 
-  patterns
+fn read_file() -> Vec<Pattern> {
+  parse_input(&reader::read(2023, 13))
 }
 
 fn get_mirror_row_index(pattern: &Pattern) -> Option<usize> {
@@ -100,6 +87,11 @@ pub fn part_1() -> usize {
   solve_part_1(&patterns)
 }
 
+pub fn part_1_from_input(input: &str) -> usize {
+  let patterns = parse_input(input);
+  solve_part_1(&patterns)
+}
+
 // we repeat most of the functionality of part 1, to now work assuming there is a "smudge".
 // The below function looks a little complex but it's basically the same as the "smudge-less" version,
 // except that:
@@ -163,3 +155,8 @@ pub fn part_2() -> usize {
   let patterns = read_file();
   solve_part_2(&patterns)
 }
+
+pub fn part_2_from_input(input: &str) -> usize {
+  let patterns = parse_input(input);
+  solve_part_2(&patterns)
+}
@@ -1,6 +1,5 @@
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::prelude::*;
+use crate::solutions::reader;
 
 #[derive(Clone, Copy)]
 enum Rock {
@@ -163,11 +162,8 @@ impl Rocks {
   }
 }
 
-fn read_file() -> Rocks {
-  let mut file = File::open("./input/input14.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
-
+fn parse_input(contents: &str) -> Rocks {
+  let contents: &str = &reader::normalize(contents);
   let mut grid = vec![];
   for line in contents.lines() {
     let mut row = vec![];
@@ -186,6 +182,10 @@ fn read_file() -> Rocks {
   Rocks { grid }
 }
 
+fn read_file() -> Rocks {
+  parse_input(&reader::read(2023, 14))
+}
+
 fn solve_part_1(rocks: &mut Rocks) -> usize {
   rocks.roll_all_north();
   rocks.total_load_north()
@@ -196,14 +196,22 @@ pub fn part_1() -> usize {
   solve_part_1(&mut rocks)
 }
 
+pub fn part_1_from_input(input: &str) -> usize {
+  let mut rocks = parse_input(input);
+  solve_part_1(&mut rocks)
+}
+
+// the number of spin cycles in part 2
+pub const CYCLES: usize = 1000000000;
+
 // As often with AoC problems, when presented with an impossible huge number of iterations to make,
 // the solution is that a cycle occurs somewhere, allowing us to compute the result in a tiny
 // fraction of the total we need. This works with the example so presumably will (with a longer, later cycle
 // I assume) with the real data.
-fn solve_part_2(rocks: &mut Rocks) -> usize {
+fn solve_part_2(rocks: &mut Rocks, cycles: usize) -> usize {
   let mut arrangements_seen = HashMap::new();
   let mut cycle = None;
-  for i in 0..1000000000 {
+  for i in 0..cycles {
     let as_str = rocks.get_string_representation();
     let previous_cycle_seen = arrangements_seen.get(&as_str).map(|&n| n);
     if let Some(prev_index) = previous_cycle_seen {
@@ -217,14 +225,14 @@ fn solve_part_2(rocks: &mut Rocks) -> usize {
     None => {
       // this hopefully won't happen in practice - it means we've actually gone through all billion cycles.
       // If this somehow happened before the heat-death of the universe (or, less likely, within a few hours),
-      // we can just read the answer
+      // we can just read the answer. (It does of course happen when asking for only a few cycles.)
       rocks.total_load_north()
     },
     Some((start, end)) => {
       // compute the smallest number of cycles we need to do to get to the same state as
       // after a billion
       let cycle_length = end - start;
-      let remaining = (1_000_000_000 - end) % cycle_length;
+      let remaining = (cycles - end) % cycle_length;
       for _ in 0..remaining {
         rocks.complete_cyle();
       }
@@ -235,5 +243,10 @@ fn solve_part_2(rocks: &mut Rocks) -> usize {
 
 pub fn part_2() -> usize {
   let mut rocks = read_file();
-  solve_part_2(&mut rocks)
+  solve_part_2(&mut rocks, CYCLES)
+}
+
+pub fn part_2_from_input(input: &str, cycles: usize) -> usize {
+  let mut rocks = parse_input(input);
+  solve_part_2(&mut rocks, cycles)
 }
@@ -1,14 +1,14 @@
-use std::fs::File;
-use std::io::prelude::*;
-
-fn read_file() -> Vec<String> {
-  let mut file = File::open("./input/input15.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
+use crate::solutions::reader;
 
+fn parse_input(contents: &str) -> Vec<String> {
+  let contents: &str = &reader::normalize(contents);
   contents.split(",").map(|s| s.to_owned()).collect()
 }
 
+fn read_file() -> Vec<String> {
+  parse_input(&reader::read(2023, 15))
+}
+
 fn hash(instruction: &str) -> usize {
   let mut current = 0;
 
@@ -30,6 +30,11 @@ pub fn part_1() -> usize {
   solve_part_1(instructions)
 }
 
+pub fn part_1_from_input(input: &str) -> usize {
+  let instructions = parse_input(input);
+  solve_part_1(instructions)
+}
+
 enum Instruction {
   Remove(String),
   Place(String, usize),
@@ -146,3 +151,8 @@ pub fn part_2() -> usize {
   let instructions = read_file();
   solve_part_2(instructions)
 }
+
+pub fn part_2_from_input(input: &str) -> usize {
+  let instructions = parse_input(input);
+  solve_part_2(instructions)
+}
@@ -1,6 +1,6 @@
-use std::fs::File;
-use std::io::prelude::*;
 use std::collections::{HashMap, HashSet};
+use crate::solutions::picture::Picture;
+use crate::solutions::reader;
 
 #[derive(Clone, Copy)]
 enum Mirror {
@@ -10,11 +10,8 @@ enum Mirror {
   SplitterHorizontal,
 }
 
-fn read_file() -> Vec<Vec<Option<Mirror>>> {
-  let mut file = File::open("./input/input16.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
-
+fn parse_input(contents: &str) -> Vec<Vec<Option<Mirror>>> {
+  let contents: &str = &reader::normalize(contents);
   let mut grid = vec![];
   for line in contents.lines() {
     let mut row = vec![];
@@ -35,6 +32,10 @@ fn read_file() -> Vec<Vec<Option<Mirror>>> {
   grid
 }
 
+fn read_file() -> Vec<Vec<Option<Mirror>>> {
+  parse_input(&reader::read(2023, 16))
+}
+
 #[derive(PartialEq, Eq, Hash, Clone, Copy)]
 enum Direction {
   North,
@@ -151,6 +152,11 @@ pub fn part_1() -> usize {
   solve_part_1(grid)
 }
 
+pub fn part_1_from_input(input: &str) -> usize {
+  let grid = parse_input(input);
+  solve_part_1(grid)
+}
+
 fn solve_part_2(grid: Vec<Vec<Option<Mirror>>>) -> usize {
   let mut maximum = 0;
   // test left edge going East
@@ -196,3 +202,30 @@ pub fn part_2() -> usize {
   let grid = read_file();
   solve_part_2(grid)
 }
+
+pub fn part_2_from_input(input: &str) -> usize {
+  let grid = parse_input(input);
+  solve_part_2(grid)
+}
+
+// for the report: the tiles energized in part 1 (in yellow - or orange if there's a mirror or splitter there), and
+// the mirrors and splitters the beam never reaches (in grey)
+pub fn render_from_input(input: &str) -> Picture {
+  let grid = parse_input(input);
+  let mut tracer = BeamTracer { grid, beams: HashMap::new() };
+  tracer.move_from(0, 0, Direction::East);
+  let cells = tracer
+    .grid
+    .iter()
+    .enumerate()
+    .map(|(row, contents)| {
+      contents.iter().enumerate().map(|(col, mirror)| match (tracer.beams.contains_key(&(row, col)), mirror) {
+        (true, Some(_)) => Some("#ff8800"),
+        (true, None) => Some("#ffdd33"),
+        (false, Some(_)) => Some("#999999"),
+        (false, None) => None,
+      }).collect()
+    })
+    .collect();
+  Picture::Grid(cells)
+}
@@ -1,12 +1,10 @@
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::prelude::*;
-
-fn read_file() -> Vec<Vec<u32>> {
-  let mut file = File::open("./input/input17.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use crate::profile;
+use crate::solutions::reader;
 
+fn parse_input(contents: &str) -> Vec<Vec<u32>> {
+  let contents: &str = &reader::normalize(contents);
   let mut grid = vec![];
   for line in contents.lines() {
     let mut row = vec![];
@@ -19,6 +17,10 @@ fn read_file() -> Vec<Vec<u32>> {
   grid
 }
 
+fn read_file() -> Vec<Vec<u32>> {
+  parse_input(&reader::read(2023, 17))
+}
+
 // going to use Djikstra's algorithm to find the best path (https://en.wikipedia.org/wiki/Dijkstra%27s_algorithm)
 // To avoid having to commit to a particular implementation of the priority queue early, going to use a trait
 // with the necessary methods, that will be all we use in the main algorithm - then I can more easily swap the
@@ -29,7 +31,8 @@ trait MinPriorityQueue<T: Copy> {
 
   fn decrease_priority(&mut self, node: T, new_priority: u32);
 
-  fn extract_minimum(&mut self) -> (T, u32);
+  // None once the queue is empty
+  fn extract_minimum(&mut self) -> Option<(T, u32)>;
 
   fn get_priority(&self, node: T) -> Option<u32>;
 
@@ -51,7 +54,7 @@ trait MinPriorityQueue<T: Copy> {
   fn new() -> Self;
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 enum Direction {
   North,
   South,
@@ -63,7 +66,7 @@ enum Direction {
 // we're going in - because the direction determines which nodes we can actually get to next.
 // However, we store the start and end as special cases, because in neither of these cases does direction matter
 // and, more importantly, when we "end" node signifies that we can stop, once that becomes the "current node".
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 enum Node {
   Start,
   End,
@@ -86,7 +89,7 @@ impl MinPriorityQueue<Node> for HashmapQueue {
     self.0.insert(node, new_priority);
   }
 
-  fn extract_minimum(&mut self) -> (Node, u32) {
+  fn extract_minimum(&mut self) -> Option<(Node, u32)> {
     let mut min: Option<(Node, u32)> = None;
 
     for (node, distance) in self.0.clone() {
@@ -95,9 +98,9 @@ impl MinPriorityQueue<Node> for HashmapQueue {
       }
     }
 
-    self.0.remove(&min.unwrap().0);
+    self.0.remove(&min?.0);
 
-    min.unwrap()
+    min
   }
 
   fn get_priority(&self, node: Node) -> Option<u32> {
@@ -109,6 +112,43 @@ impl MinPriorityQueue<Node> for HashmapQueue {
   }
 }
 
+// the "proper" implementation, using a binary heap. The heap doesn't support decreasing a priority in place, so
+// instead we just push the node again with its new priority, and keep a hashmap of the current priority of everything
+// still in the queue so that out-of-date heap entries can be skipped over when they come to the top.
+struct BinaryHeapQueue {
+  heap: BinaryHeap<Reverse<(u32, Node)>>,
+  priorities: HashMap<Node, u32>,
+}
+
+impl MinPriorityQueue<Node> for BinaryHeapQueue {
+  fn add_node(&mut self, node: Node, priority: u32) {
+    self.heap.push(Reverse((priority, node)));
+    self.priorities.insert(node, priority);
+  }
+
+  fn decrease_priority(&mut self, node: Node, new_priority: u32) {
+    self.add_node(node, new_priority);
+  }
+
+  fn extract_minimum(&mut self) -> Option<(Node, u32)> {
+    loop {
+      let Reverse((priority, node)) = self.heap.pop()?;
+      if self.priorities.get(&node) == Some(&priority) {
+        self.priorities.remove(&node);
+        return Some((node, priority));
+      }
+    }
+  }
+
+  fn get_priority(&self, node: Node) -> Option<u32> {
+    self.priorities.get(&node).copied()
+  }
+
+  fn new() -> Self {
+    Self { heap: BinaryHeap::new(), priorities: HashMap::new() }
+  }
+}
+
 // a helper function which makes the expression of the main algorithm a lot nicer, particularly in avoiding
 // repetition between parts 1 and 2!
 fn move_in_direction(queue: &mut impl MinPriorityQueue<Node>, grid: &Vec<Vec<u32>>, current_row: usize, current_col: usize, direction: Direction, min_distance: usize, max_distance: usize, current_min: u32) {
@@ -130,10 +170,10 @@ fn move_in_direction(queue: &mut impl MinPriorityQueue<Node>, grid: &Vec<Vec<u32
     Direction::South => {
       let mut new_distance = current_min;
       for i in 1..=max_distance {
-        if current_row <= grid.len() - i - 1 {
+        if current_row + i < grid.len() {
           new_distance += grid[current_row + i][current_col];
           if i >= min_distance {
-            let new_node = if current_row == grid.len() - i - 1 && current_col == grid[0].len() - 1 { Node::End } else { Node::Other(current_row + i, current_col, Direction::South) };
+            let new_node = if current_row + i == grid.len() - 1 && current_col == grid[0].len() - 1 { Node::End } else { Node::Other(current_row + i, current_col, Direction::South) };
             queue.add_or_update_node(new_node, new_distance);
           }
         }
@@ -142,10 +182,10 @@ fn move_in_direction(queue: &mut impl MinPriorityQueue<Node>, grid: &Vec<Vec<u32
     Direction::East => {
       let mut new_distance = current_min;
       for i in 1..=max_distance {
-        if current_col <= grid[0].len() - i - 1 {
+        if current_col + i < grid[0].len() {
           new_distance += grid[current_row][current_col + i];
           if i >= min_distance {
-            let new_node = if current_col == grid[0].len() - i - 1 && current_row == grid.len() - 1 { Node::End } else { Node::Other(current_row, current_col + i, Direction::East) };
+            let new_node = if current_col + i == grid[0].len() - 1 && current_row == grid.len() - 1 { Node::End } else { Node::Other(current_row, current_col + i, Direction::East) };
             queue.add_or_update_node(new_node, new_distance);
           }
         }
@@ -169,7 +209,7 @@ fn move_in_direction(queue: &mut impl MinPriorityQueue<Node>, grid: &Vec<Vec<u32
 // the general form of the algorithm, using a generic priority queue implementation.
 // Takes as arguments the min and max distance the "crucibles" can travel in, so this can be used
 // for both parts of the problem
-fn solve_with_djikstra<Q: MinPriorityQueue<Node>>(grid: Vec<Vec<u32>>, min_distance: usize, max_distance: usize) -> u32 {
+fn solve_with_djikstra<Q: MinPriorityQueue<Node>>(grid: Vec<Vec<u32>>, min_distance: usize, max_distance: usize) -> Option<u32> {
   let mut queue = Q::new();
   queue.add_node(Node::Start, 0);
   let mut current = (Node::Start, 0);
@@ -177,6 +217,7 @@ fn solve_with_djikstra<Q: MinPriorityQueue<Node>>(grid: Vec<Vec<u32>>, min_dista
 
   loop {
     let (current_node, current_min) = current;
+    profile::count("nodes expanded");
     visited.push(current_node);
     let valid_directions = match current_node {
       Node::End => break,
@@ -229,28 +270,76 @@ fn solve_with_djikstra<Q: MinPriorityQueue<Node>>(grid: Vec<Vec<u32>>, min_dista
       }
     }
 
+    // running out of nodes before getting to the end means there's no way there at all - which can happen with
+    // distances that don't fit the grid, like a minimum bigger than it
     while visited.contains(&current.0) {
-      current = queue.extract_minimum();
+      current = queue.extract_minimum()?;
     }
   }
 
-  queue.extract_minimum().1
+  // the end has just been taken off the queue, so its distance is the one we're after - not whatever's next
+  Some(current.1)
 }
 
-fn solve_part_1(grid: Vec<Vec<u32>>) -> u32 {
-  solve_with_djikstra::<HashmapQueue>(grid, 1, 3)
+// the answer to either part - the least heat lost on the way, or that there's no way to the end
+pub fn describe(heat_loss: Option<u32>) -> String {
+  heat_loss.map_or(String::from("no path"), |heat_loss| heat_loss.to_string())
 }
 
-pub fn part_1() -> u32 {
+// how far the crucibles can go in a straight line - at least the minimum before turning, and no more than the maximum
+pub const PART_1_MIN_DISTANCE: usize = 1;
+pub const PART_1_MAX_DISTANCE: usize = 3;
+pub const PART_2_MIN_DISTANCE: usize = 4;
+pub const PART_2_MAX_DISTANCE: usize = 10;
+
+// The distances have to allow some move at all, and the least a crucible has to go in a straight line can't be
+// more than the most it can. These are the `--part-N-min-distance` and `--part-N-max-distance` parameters.
+pub fn check_distances(part: u8, min_distance: usize, max_distance: usize) -> Result<(), String> {
+  if min_distance == 0 {
+    Err(format!("--part-{}-min-distance has to be at least 1", part))
+  } else if min_distance > max_distance {
+    Err(format!("--part-{0}-min-distance ({1}) can't be more than --part-{0}-max-distance ({2})", part, min_distance, max_distance))
+  } else {
+    Ok(())
+  }
+}
+
+fn solve_part_1(grid: Vec<Vec<u32>>, min_distance: usize, max_distance: usize) -> Option<u32> {
+  solve_with_djikstra::<HashmapQueue>(grid, min_distance, max_distance)
+}
+
+pub fn part_1() -> Option<u32> {
   let grid = read_file();
-  solve_part_1(grid)
+  solve_part_1(grid, PART_1_MIN_DISTANCE, PART_1_MAX_DISTANCE)
 }
 
-fn solve_part_2(grid: Vec<Vec<u32>>) -> u32 {
-  solve_with_djikstra::<HashmapQueue>(grid, 4, 10)
+fn solve_part_2(grid: Vec<Vec<u32>>, min_distance: usize, max_distance: usize) -> Option<u32> {
+  solve_with_djikstra::<HashmapQueue>(grid, min_distance, max_distance)
 }
 
-pub fn part_2() -> u32 {
+pub fn part_2() -> Option<u32> {
   let grid = read_file();
-  solve_part_2(grid)
+  solve_part_2(grid, PART_2_MIN_DISTANCE, PART_2_MAX_DISTANCE)
+}
+
+pub fn part_1_from_input(input: &str, min_distance: usize, max_distance: usize) -> Option<u32> {
+  let grid = parse_input(input);
+  solve_part_1(grid, min_distance, max_distance)
+}
+
+pub fn part_2_from_input(input: &str, min_distance: usize, max_distance: usize) -> Option<u32> {
+  let grid = parse_input(input);
+  solve_part_2(grid, min_distance, max_distance)
+}
+
+// the same two parts using the binary heap queue. These aren't used for the answers yet - they're checked against
+// the hashmap versions above by the differential tests first.
+pub fn part_1_with_heap_from_input(input: &str, min_distance: usize, max_distance: usize) -> Option<u32> {
+  let grid = parse_input(input);
+  solve_with_djikstra::<BinaryHeapQueue>(grid, min_distance, max_distance)
+}
+
+pub fn part_2_with_heap_from_input(input: &str, min_distance: usize, max_distance: usize) -> Option<u32> {
+  let grid = parse_input(input);
+  solve_with_djikstra::<BinaryHeapQueue>(grid, min_distance, max_distance)
 }
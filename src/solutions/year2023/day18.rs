@@ -1,6 +1,6 @@
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::prelude::*;
+use crate::solutions::picture::Picture;
+use crate::solutions::reader;
 
 #[derive(PartialEq, Eq, Hash)]
 enum Direction {
@@ -118,14 +118,15 @@ fn get_trench_coords(instructions: &Vec<DigInstruction>) -> HashSet<(isize, isiz
   result
 }
 
-fn read_file() -> Vec<DigInstruction> {
-  let mut file = File::open("./input/input18.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
-
+fn parse_input(contents: &str) -> Vec<DigInstruction> {
+  let contents: &str = &reader::normalize(contents);
   contents.lines().map(read_line).collect()
 }
 
+fn read_file() -> Vec<DigInstruction> {
+  parse_input(&reader::read(2023, 18))
+}
+
 fn solve_part_1(instructions: Vec<DigInstruction>) -> u64 {
   // solve this the same way as day 10, part 2. In order to count crossing more easily, we use
   // the "annotated" instructions.
@@ -238,6 +239,11 @@ pub fn part_1() -> u64 {
   solve_part_1(instructions)
 }
 
+pub fn part_1_from_input(input: &str) -> u64 {
+  let instructions = parse_input(input);
+  solve_part_1(instructions)
+}
+
 fn convert_hex(instructions: Vec<DigInstruction>) -> Vec<DigInstruction> {
   instructions.iter().map(|instruction| {
     let color = &instruction.color;
@@ -263,3 +269,25 @@ pub fn part_2() -> u64 {
   let instructions = read_file();
   solve_part_2(instructions)
 }
+
+pub fn part_2_from_input(input: &str) -> u64 {
+  let instructions = parse_input(input);
+  solve_part_2(instructions)
+}
+
+// for the report: the outline of the lagoon from part 1, with each stretch of trench in the colour it was painted
+pub fn render_from_input(input: &str) -> Picture {
+  let instructions = parse_input(input);
+  let mut corners = vec![];
+  let (mut row, mut column) = (0, 0);
+  for DigInstruction { direction, distance, color } in instructions.iter() {
+    corners.push(((column as i64, row as i64), color.clone()));
+    match direction {
+      Direction::Up => row -= distance,
+      Direction::Down => row += distance,
+      Direction::Left => column -= distance,
+      Direction::Right => column += distance,
+    }
+  }
+  Picture::Shape(corners)
+}
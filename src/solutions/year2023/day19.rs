@@ -1,6 +1,6 @@
+use crate::trace::{self, Level};
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::prelude::*;
+use crate::solutions::reader;
 
 struct Part {
   x: u64,
@@ -91,33 +91,25 @@ fn parse_rule(text: &str) -> Rule {
   Rule { destination, test }
 }
 
-fn read_file() -> PuzzleData {
-  let mut file = File::open("./input/input19.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
-
-  let mut parts = vec![];
+fn parse_input(contents: &str) -> PuzzleData {
+  let contents: &str = &reader::normalize(contents);
+  // the workflows come first, then the parts
+  let sections = reader::sections(contents);
   let mut rules = HashMap::new();
-  let mut finished_rules = false;
-
-  for line in contents.lines() {
-    if line.is_empty() {
-      finished_rules = true;
-      continue;
-    }
-
-    if finished_rules {
-      parts.push(parse_part(line));
-    } else {
-      let parts: Vec<&str> = line.split(['{', '}']).collect();
-      let label = parts[0].to_owned();
-      rules.insert(label, parts[1].split(",").map(parse_rule).collect());
-    }
+  for line in sections[0].lines() {
+    let parts: Vec<&str> = line.split(['{', '}']).collect();
+    let label = parts[0].to_owned();
+    rules.insert(label, parts[1].split(",").map(parse_rule).collect());
   }
+  let parts = sections.get(1).map_or(vec![], |section| section.lines().map(parse_part).collect());
 
   PuzzleData { parts, rules }
 }
 
+fn read_file() -> PuzzleData {
+  parse_input(&reader::read(2023, 19))
+}
+
 fn apply_test(part: &Part, test: &Test) -> bool {
   let Test { test_property, test_operation, test_comparison } = test;
   let value_to_test = match test_property {
@@ -151,23 +143,38 @@ fn apply_ruleset(part: &Part, rules: &Vec<Rule>) -> PartDestination {
   panic!("no destination found after all rules applied!");
 }
 
+fn describe_part(part: &Part) -> String {
+  format!("{{x={},m={},a={},s={}}}", part.x, part.m, part.a, part.s)
+}
+
 fn is_part_accepted(part: &Part, rules: &HashMap<String, Vec<Rule>>) -> bool {
   let mut destination = String::from("in");
+  // the workflows the part goes through, just for explaining
+  let mut route = vec![destination.clone()];
 
-  loop {
+  let accepted = loop {
     let ruleset = rules.get(&destination).unwrap();
     match apply_ruleset(part, ruleset) {
-      PartDestination::Accepted => return true,
-      PartDestination::Rejected => return false,
+      PartDestination::Accepted => break true,
+      PartDestination::Rejected => break false,
       PartDestination::Rule(label) => destination = label,
     }
-  }
+    route.push(destination.clone());
+  };
+  trace::emit(Level::Detail, "part route", &[("part", &describe_part(part)), ("route", &route.join(",")), ("accepted", &accepted)], || {
+    format!("{}: {} -> {}", describe_part(part), route.join(" -> "), if accepted { "A" } else { "R" })
+  });
+  accepted
 }
 
 fn solve_part_1(data: &PuzzleData) -> u64 {
   let PuzzleData { parts, rules } = data;
 
-  parts.iter().filter(|part| is_part_accepted(part, rules)).map(|part| part.x + part.m + part.a + part.s).sum()
+  let accepted: Vec<&Part> = parts.iter().filter(|part| is_part_accepted(part, rules)).collect();
+  trace::emit(Level::Summary, "accepted parts", &[("accepted", &accepted.len()), ("total", &parts.len())], || {
+    format!("{} of the {} parts are accepted", accepted.len(), parts.len())
+  });
+  accepted.iter().map(|part| part.x + part.m + part.a + part.s).sum()
 }
 
 pub fn part_1() -> u64 {
@@ -175,6 +182,11 @@ pub fn part_1() -> u64 {
   solve_part_1(&data)
 }
 
+pub fn part_1_from_input(input: &str) -> u64 {
+  let data = parse_input(input);
+  solve_part_1(&data)
+}
+
 // for part 2, it should simply be a cause of starting from "in", following each path we can go down,
 // and keeping track of the region of 4-dimensional space (ie the valid values of the 4 paramaters)
 // that we must be in in order to get to each destination. All paths end with R or A, so we just need
@@ -314,8 +326,22 @@ fn get_accepted_volume(starting_volume: &FourDimensionalRegion, rules: &HashMap<
         },
         Some(test) => {
           let pass_volume = starting_volume.reduce_for_test(&test);
-          let pass_result = get_accepted_volume(&pass_volume, rules, &rule_to_consider.destination, 0);
           let fail_volume = starting_volume.reduce_for_test_fail(&test);
+          let condition = format!("{}{}{}", test.test_property, test.test_operation, test.test_comparison);
+          trace::emit(Level::Detail, "workflow branch", &[("workflow", label), ("rule", &current_index), ("condition", &condition)], || {
+            let size = |region: &FourDimensionalRegion| if region.is_empty() { 0 } else { region.volume() };
+            format!(
+              "{} rule {}: {} of the {} combinations here pass {} and go to {}, the other {} carry on",
+              label,
+              current_index + 1,
+              size(&pass_volume),
+              starting_volume.volume(),
+              condition,
+              describe_destination(&rule_to_consider.destination),
+              size(&fail_volume)
+            )
+          });
+          let pass_result = get_accepted_volume(&pass_volume, rules, &rule_to_consider.destination, 0);
           let fail_result = get_accepted_volume(&fail_volume, rules, current, current_index + 1);
           return pass_result + fail_result;
         },
@@ -324,11 +350,28 @@ fn get_accepted_volume(starting_volume: &FourDimensionalRegion, rules: &HashMap<
   }
 }
 
+fn describe_destination(destination: &PartDestination) -> String {
+  match destination {
+    PartDestination::Accepted => String::from("A"),
+    PartDestination::Rejected => String::from("R"),
+    PartDestination::Rule(label) => label.clone(),
+  }
+}
+
 fn solve_part_2(data: &PuzzleData) -> u64 {
-  get_accepted_volume(&FourDimensionalRegion::new(), &data.rules, &PartDestination::Rule(String::from("in")), 0)
+  let accepted = get_accepted_volume(&FourDimensionalRegion::new(), &data.rules, &PartDestination::Rule(String::from("in")), 0);
+  trace::emit(Level::Summary, "accepted combinations", &[("accepted", &accepted)], || {
+    format!("{} of the {} possible combinations end up accepted", accepted, FourDimensionalRegion::new().volume())
+  });
+  accepted
 }
 
 pub fn part_2() -> u64 {
   let data = read_file();
   solve_part_2(&data)
 }
+
+pub fn part_2_from_input(input: &str) -> u64 {
+  let data = parse_input(input);
+  solve_part_2(&data)
+}
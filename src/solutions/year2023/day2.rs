@@ -0,0 +1,432 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use crate::solutions::checked;
+use crate::solutions::reader;
+
+// Some number of cubes of each of any number of colours: either a handful revealed from a bag, or what's in the bag
+// itself. The puzzle only has red, green and blue, but any names will do. Written like "3 blue, 4 red".
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Cubes(BTreeMap<String, u32>);
+
+impl Cubes {
+  // how many there are of a colour, which is none for any colour not mentioned
+  fn count(&self, colour: &str) -> u32 {
+    self.0.get(colour).copied().unwrap_or(0)
+  }
+
+  fn total(&self) -> u32 {
+    self.0.values().sum()
+  }
+}
+
+impl Display for Cubes {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let parts: Vec<String> = self.0.iter().map(|(colour, number)| format!("{} {}", number, colour)).collect();
+    if parts.is_empty() { write!(f, "nothing") } else { write!(f, "{}", parts.join(", ")) }
+  }
+}
+
+impl FromStr for Cubes {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut result = Cubes::default();
+    for part in s.split(", ") {
+      let (number, colour) = part.split_once(' ').ok_or(format!("expected a number and a colour, got {}", part))?;
+      let number: u32 = number.parse().map_err(|_| format!("{} isn't a number of cubes", number))?;
+      *result.0.entry(colour.to_owned()).or_insert(0) += number;
+    }
+    Ok(result)
+  }
+}
+
+pub struct Game {
+  id: u32,
+  draws: Vec<Cubes>
+}
+
+fn read_line(line: &str) -> Game {
+  let parts: Vec<&str> = line.split(": ").collect();
+  let (game_id_part, reveal_parts) = parts.split_at(1);
+  let id = game_id_part[0].split(" ").collect::<Vec<&str>>()[1].parse().unwrap();
+  let draws = reveal_parts[0].split("; ").map(|s| s.parse().unwrap_or_else(|message| panic!("{}", message))).collect();
+  Game {
+    id,
+    draws
+  }
+}
+
+fn parse_input(contents: &str) -> Vec<Game> {
+  let contents: &str = &reader::normalize(contents);
+  contents.lines().map(read_line).collect()
+}
+
+fn read_file() -> Vec<Game> {
+  parse_input(&reader::read(2023, 2))
+}
+
+
+// the bag part 1 asks about
+pub const BAG: &str = "12 red, 13 green, 14 blue";
+
+fn is_possible(game: &Game, bag: &Cubes) -> bool {
+  game.draws.iter().all(|draw| draw.0.iter().all(|(colour, &number)| number <= bag.count(colour)))
+}
+
+fn solve_part_1(v: Vec<Game>, bag: &Cubes) -> u32 {
+  v.iter().filter(|game| is_possible(game, bag)).map(|game| game.id).sum()
+}
+
+pub fn part_1() -> u32 {
+  let games = read_file();
+  solve_part_1(games, &BAG.parse().unwrap())
+}
+
+pub fn part_1_from_input(input: &str, bag: &Cubes) -> u32 {
+  let games = parse_input(input);
+  solve_part_1(games, bag)
+}
+
+// the fewest cubes of each colour the bag could have held for the game to be possible
+fn get_minimum_bag(game: &Game) -> Cubes {
+  let mut bag = Cubes::default();
+  for draw in game.draws.iter() {
+    for (colour, &number) in draw.0.iter() {
+      let most = bag.0.entry(colour.clone()).or_insert(0);
+      *most = (*most).max(number);
+    }
+  }
+  bag
+}
+
+// The power multiplies together the minimum number of each colour - every colour seen anywhere in the input, so
+// that (just like with red, green and blue) a game that never shows some colour has a power of 0.
+fn get_minimum_power(game: &Game, colours: &BTreeSet<&str>) -> u64 {
+  let bag = get_minimum_bag(game);
+  colours.iter().fold(1u64, |power, colour| checked!(mul, power, bag.count(colour) as u64))
+}
+
+fn solve_part_2(v: Vec<Game>) -> u64 {
+  let colours: BTreeSet<&str> = v.iter().flat_map(|game| game.draws.iter().flat_map(|draw| draw.0.keys().map(|colour| colour.as_str()))).collect();
+  v.iter().map(|game| get_minimum_power(game, &colours)).fold(0u64, |total, power| checked!(add, total, power))
+}
+
+pub fn part_2() -> u64 {
+  let games = read_file();
+  solve_part_2(games)
+}
+
+pub fn part_2_from_input(input: &str) -> u64 {
+  let games = parse_input(input);
+  solve_part_2(games)
+}
+
+// The amount of each colour that's most likely to have been in the bag for a game, as opposed to the least it could
+// have been. Each reveal is a handful drawn from the bag without replacement (and put back afterwards), so with a
+// bag of b_c cubes of each colour c, N in all, a reveal of r_c of each, n in all, has probability
+// product(C(b_c, r_c)) / C(N, n). A bag's likelihood is the product of that over the game's reveals, and every bag
+// from the minimum up to `max_cubes` of each colour is tried. The bounds are those of the bags whose likelihood is
+// within a factor of e^1.92 of the best - the usual likelihood-ratio interval, which is about 95% confidence.
+pub struct Estimate {
+  pub bag: Cubes,
+  pub bounds: BTreeMap<String, (u32, u32)>,
+  // whether the best bag or the bounds reached `max_cubes` of some colour, so could really go higher still
+  pub at_limit: bool,
+}
+
+// the default largest number of cubes of any one colour that estimates consider
+pub const MAX_CUBES: u32 = 40;
+
+// Every bag is tried twice over, so the number of them has to be kept within reason: the default of up to 40 of
+// each of the three colours is about 69,000 bags.
+const MOST_BAGS_ESTIMATED: u64 = 10_000_000;
+const MOST_CUBES_ESTIMATED: u32 = 10_000;
+
+// half the 95% point of the chi-squared distribution with one degree of freedom
+const LOG_LIKELIHOOD_DROP: f64 = 1.92;
+
+// ln(n!) for every n up to the one given, to make the binomial coefficients from
+fn ln_factorials(up_to: u32) -> Vec<f64> {
+  let mut result = vec![0.0];
+  for n in 1..=up_to {
+    result.push(result[n as usize - 1] + (n as f64).ln());
+  }
+  result
+}
+
+fn log_likelihood(game: &Game, colours: &[&str], amounts: &[u32], ln_factorial: &[f64]) -> f64 {
+  let ln_choose = |n: u32, k: u32| ln_factorial[n as usize] - ln_factorial[k as usize] - ln_factorial[(n - k) as usize];
+  let total: u32 = amounts.iter().sum();
+  game.draws.iter().map(|draw| {
+    let chosen: f64 = colours.iter().zip(amounts).map(|(colour, &amount)| ln_choose(amount, draw.count(colour))).sum();
+    chosen - ln_choose(total, draw.total())
+  }).sum()
+}
+
+// Calls `visit` with every bag in turn from `lowest` up to `highest` of each colour, counting up through the amounts
+// like an odometer.
+fn for_each_bag(lowest: &[u32], highest: &[u32], mut visit: impl FnMut(&[u32])) {
+  let mut amounts = lowest.to_vec();
+  loop {
+    visit(&amounts);
+    let Some(index) = (0..amounts.len()).find(|&index| amounts[index] < highest[index]) else { break };
+    amounts[index] += 1;
+    amounts[..index].copy_from_slice(&lowest[..index]);
+  }
+}
+
+pub fn estimate_bag(game: &Game, max_cubes: u32) -> Result<Estimate, String> {
+  // a colour that never turns up is most likely not in the bag at all, so only the ones seen are considered
+  let minimum = get_minimum_bag(game);
+  let colours: Vec<&str> = minimum.0.keys().map(|colour| colour.as_str()).collect();
+  let lowest: Vec<u32> = colours.iter().map(|colour| minimum.count(colour)).collect();
+  let highest: Vec<u32> = lowest.iter().map(|&amount| amount.max(max_cubes)).collect();
+  if let Some(amount) = highest.iter().find(|&&amount| amount > MOST_CUBES_ESTIMATED) {
+    return Err(format!("can't estimate bags with {} cubes of a colour - at most {} will do", amount, MOST_CUBES_ESTIMATED));
+  }
+  let bags = lowest.iter().zip(&highest).try_fold(1u64, |bags, (&low, &high)| bags.checked_mul((high - low + 1) as u64));
+  if bags.is_none_or(|bags| bags > MOST_BAGS_ESTIMATED) {
+    return Err(format!(
+      "{} colours with up to {} cubes of each is too many bags to try (at most {}) - try a smaller --max-cubes",
+      colours.len(), max_cubes, MOST_BAGS_ESTIMATED
+    ));
+  }
+  let ln_factorial = ln_factorials(highest.iter().sum());
+
+  // the most likely bag - or the smallest of those, if several are just as likely
+  let mut best = (lowest.clone(), f64::NEG_INFINITY);
+  for_each_bag(&lowest, &highest, |amounts| {
+    let likelihood = log_likelihood(game, &colours, amounts, &ln_factorial);
+    let smaller = amounts.iter().sum::<u32>() < best.0.iter().sum::<u32>();
+    if likelihood > best.1 + 1e-9 || (likelihood > best.1 - 1e-9 && smaller) {
+      best = (amounts.to_vec(), likelihood);
+    }
+  });
+  // and then every bag nearly as likely, a second time round rather than keeping them all from the first
+  let mut bounds: Vec<(u32, u32)> = best.0.iter().map(|&amount| (amount, amount)).collect();
+  for_each_bag(&lowest, &highest, |amounts| {
+    if log_likelihood(game, &colours, amounts, &ln_factorial) >= best.1 - LOG_LIKELIHOOD_DROP {
+      for (bound, &amount) in bounds.iter_mut().zip(amounts) {
+        *bound = (bound.0.min(amount), bound.1.max(amount));
+      }
+    }
+  });
+  Ok(Estimate {
+    bag: Cubes(colours.iter().zip(&best.0).map(|(colour, &amount)| (colour.to_string(), amount)).collect()),
+    at_limit: bounds.iter().zip(&highest).any(|(bound, &highest)| bound.1 == highest),
+    bounds: colours.iter().zip(bounds).map(|(colour, bound)| (colour.to_string(), bound)).collect(),
+  })
+}
+
+fn describe_estimate(estimate: &Estimate) -> String {
+  let bounds: Vec<String> = estimate.bounds.iter().map(|(colour, (low, high))| format!("{} {}-{}", colour, low, high)).collect();
+  let limit = if estimate.at_limit { " - reaching the most tried, so perhaps more" } else { "" };
+  format!("most likely {} (95% bounds: {}{})", estimate.bag, bounds.join(", "), limit)
+}
+
+// A made-up game: a random bag of red, green and blue cubes, with a few random reveals drawn from it. Gives the
+// game and what was really in the bag.
+fn random_game(rng: &mut StdRng, id: u32, max_cubes: u32) -> (Game, Cubes) {
+  let colours = ["red", "green", "blue"];
+  let amounts: Vec<u32> = colours.iter().map(|_| rng.gen_range(1..=(max_cubes / 2).max(1))).collect();
+  let cubes: Vec<&str> = colours.iter().zip(&amounts).flat_map(|(colour, &amount)| vec![*colour; amount as usize]).collect();
+  let draws = (0..rng.gen_range(3..=6)).map(|_| {
+    let size = rng.gen_range(1..=cubes.len());
+    let handful = cubes.choose_multiple(rng, size);
+    Cubes(handful.fold(BTreeMap::new(), |mut counts, colour| {
+      *counts.entry(colour.to_string()).or_insert(0) += 1;
+      counts
+    }))
+  }).collect();
+  (Game { id, draws }, Cubes(colours.iter().zip(amounts).map(|(colour, amount)| (colour.to_string(), amount)).collect()))
+}
+
+// how close the estimates came to the real bags over some made-up games
+struct Simulation {
+  seed: u64,
+  runs: usize,
+  // games whose most likely bag was exactly right
+  exact: usize,
+  // colours (over all the games) whose real amount was within the bounds, out of `colours`
+  covered: usize,
+  colours: usize,
+  total_error: u32,
+}
+
+impl Simulation {
+  fn coverage(&self) -> f64 {
+    100.0 * self.covered as f64 / self.colours.max(1) as f64
+  }
+}
+
+impl Display for Simulation {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{} simulated games (seed {}): the most likely bag was exactly right for {:.1}%, the real amount was within the \
+       bounds for {:.1}% of colours, and the average error was {:.2} cubes per colour",
+      self.runs, self.seed, 100.0 * self.exact as f64 / self.runs.max(1) as f64, self.coverage(),
+      self.total_error as f64 / self.colours.max(1) as f64
+    )
+  }
+}
+
+// A check that the estimates are any good: makes up random bags, draws random reveals from them, and sees how close
+// the estimates come to the real bags. The same seed always gives the same games.
+fn simulate(seed: u64, runs: usize, max_cubes: u32) -> Result<Simulation, String> {
+  let mut rng = StdRng::seed_from_u64(seed);
+  let mut simulation = Simulation { seed, runs, exact: 0, covered: 0, colours: 0, total_error: 0 };
+  for id in 1..=runs {
+    let (game, real) = random_game(&mut rng, id as u32, max_cubes);
+    let estimate = estimate_bag(&game, max_cubes)?;
+    for (colour, &amount) in real.0.iter() {
+      let (low, high) = estimate.bounds.get(colour).copied().unwrap_or((0, 0));
+      simulation.covered += (low <= amount && amount <= high) as usize;
+      simulation.colours += 1;
+      simulation.total_error += estimate.bag.count(colour).abs_diff(amount);
+    }
+    simulation.exact += (estimate.bag == real) as usize;
+  }
+  Ok(simulation)
+}
+
+// Questions about which bags the games could have been played with, for the `bags` command.
+pub enum Query {
+  // which games are possible with this bag?
+  Feasible(Cubes),
+  // what's the smallest bag that makes all of these games (by id) possible?
+  Minimal(Vec<u32>),
+  // with this many cubes in all, which bag makes the most games possible?
+  Best(u32),
+  // what's most likely to have been in the bag for each game (or just the one given), with up to this many of a
+  // colour?
+  Infer(Option<u32>, u32),
+  // how good are those estimates on made-up games? With the seed, how many games and the most of a colour.
+  Simulate(u64, usize, u32),
+}
+
+fn feasible_games(games: &[Game], bag: &Cubes) -> Vec<u32> {
+  games.iter().filter(|game| is_possible(game, bag)).map(|game| game.id).collect()
+}
+
+fn minimal_bag(games: &[Game], ids: &[u32]) -> Result<Cubes, String> {
+  let mut bag = Cubes::default();
+  for id in ids {
+    let game = games.iter().find(|game| game.id == *id).ok_or(format!("there's no game {}", id))?;
+    for (colour, number) in get_minimum_bag(game).0 {
+      let most = bag.0.entry(colour).or_insert(0);
+      *most = (*most).max(number);
+    }
+  }
+  Ok(bag)
+}
+
+// The best bag is always just big enough for some set of games, so the only amounts of each colour worth trying
+// are none at all and each game's minimum. This tries every combination of those within the budget, colour by
+// colour, giving up on a branch once the games still possible can't beat the best found so far. Of the bags that
+// make the most games possible, it's the one with the fewest cubes.
+fn best_bag(games: &[Game], budget: u32) -> (Cubes, Vec<u32>) {
+  let minimums: Vec<Cubes> = games.iter().map(get_minimum_bag).collect();
+  let colours: Vec<&str> = minimums.iter().flat_map(|bag| bag.0.keys().map(|colour| colour.as_str())).collect::<BTreeSet<&str>>().into_iter().collect();
+  let mut best = (Cubes::default(), feasible_games(games, &Cubes::default()));
+  let mut bag = Cubes::default();
+  let all: Vec<usize> = (0..games.len()).collect();
+  search_bags(games, &minimums, &colours, budget, &all, &mut bag, &mut best);
+  best
+}
+
+fn search_bags(games: &[Game], minimums: &[Cubes], colours: &[&str], budget: u32, possible: &[usize], bag: &mut Cubes, best: &mut (Cubes, Vec<u32>)) {
+  if possible.len() < best.1.len() {
+    return;
+  }
+  let Some((colour, other_colours)) = colours.split_first() else {
+    if possible.len() > best.1.len() || bag.total() < best.0.total() {
+      *best = (bag.clone(), possible.iter().map(|&index| games[index].id).collect());
+    }
+    return;
+  };
+  let amounts: BTreeSet<u32> = possible.iter().map(|&index| minimums[index].count(colour)).filter(|&amount| amount <= budget).chain([0]).collect();
+  for amount in amounts {
+    let still_possible: Vec<usize> = possible.iter().copied().filter(|&index| minimums[index].count(colour) <= amount).collect();
+    if amount > 0 {
+      bag.0.insert(colour.to_string(), amount);
+    }
+    search_bags(games, minimums, other_colours, budget - amount, &still_possible, bag, best);
+    bag.0.remove(*colour);
+  }
+}
+
+fn list_ids(ids: &[u32]) -> String {
+  if ids.is_empty() { String::from("none") } else { ids.iter().map(|id| id.to_string()).collect::<Vec<String>>().join(", ") }
+}
+
+// Entry point for the `bags` command, giving the answer to the question as a few lines of text. Simulating doesn't
+// look at the input.
+pub fn query(input: &str, query: &Query) -> Result<String, String> {
+  let games = parse_input(input);
+  match query {
+    Query::Feasible(bag) => {
+      let ids = feasible_games(&games, bag);
+      Ok(format!("{} of {} games are possible with {}: {}", ids.len(), games.len(), bag, list_ids(&ids)))
+    },
+    Query::Minimal(ids) => {
+      let bag = minimal_bag(&games, ids)?;
+      Ok(format!("the smallest bag for games {} is {} ({} cubes)", list_ids(ids), bag, bag.total()))
+    },
+    Query::Best(budget) => {
+      let (bag, ids) = best_bag(&games, *budget);
+      Ok(format!(
+        "with {} cubes, {} ({} cubes) makes {} of {} games possible: {}",
+        budget, bag, bag.total(), ids.len(), games.len(), list_ids(&ids)
+      ))
+    },
+    Query::Infer(id, max_cubes) => {
+      let chosen: Vec<&Game> = games.iter().filter(|game| id.is_none_or(|id| game.id == id)).collect();
+      if chosen.is_empty() {
+        return Err(format!("there's no game {}", id.unwrap_or(0)));
+      }
+      let lines: Vec<String> = chosen.iter().map(|game| {
+        estimate_bag(game, *max_cubes).map(|estimate| format!("game {}: {}", game.id, describe_estimate(&estimate)))
+      }).collect::<Result<_, _>>()?;
+      Ok(lines.join("\n"))
+    },
+    Query::Simulate(seed, runs, max_cubes) => simulate(*seed, *runs, *max_cubes).map(|simulation| simulation.to_string()),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn the_bounds_usually_cover_the_real_bag() {
+    let simulation = simulate(2023, 100, 20).unwrap();
+    assert!(simulation.coverage() >= 85.0, "{}", simulation);
+  }
+
+  #[test]
+  fn the_most_likely_bag_is_never_below_the_minimum() {
+    let mut rng = StdRng::seed_from_u64(2023);
+    for id in 1..=100 {
+      let (game, _) = random_game(&mut rng, id, 20);
+      let minimum = get_minimum_bag(&game);
+      let estimate = estimate_bag(&game, 20).unwrap();
+      for (colour, &amount) in minimum.0.iter() {
+        assert!(estimate.bag.count(colour) >= amount, "game {}: {} is below the minimum {}", id, estimate.bag, minimum);
+      }
+    }
+  }
+
+  #[test]
+  fn intractable_estimates_are_refused() {
+    let game = read_line("Game 1: 3 red, 4 green, 1 blue, 2 yellow, 5 purple");
+    assert!(estimate_bag(&game, 40).is_err());
+    assert!(estimate_bag(&read_line("Game 2: 3 red"), u32::MAX).is_err());
+    assert!(estimate_bag(&read_line("Game 3: 4000000000 red"), 40).is_err());
+    assert!(estimate_bag(&read_line("Game 4: 3 red, 4 green"), 40).is_ok());
+  }
+}
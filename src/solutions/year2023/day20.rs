@@ -1,7 +1,7 @@
 use num::integer::lcm;
+use crate::trace::{self, Level};
 use std::collections::{HashMap, VecDeque};
-use std::fs::File;
-use std::io::prelude::*;
+use crate::solutions::reader;
 
 #[derive(Clone, Copy)]
 enum Pulse {
@@ -84,11 +84,8 @@ impl Module {
   }
 }
 
-fn read_file() -> HashMap<String, Module> {
-  let mut file = File::open("./input/input20.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
-
+fn parse_input(contents: &str) -> HashMap<String, Module> {
+  let contents: &str = &reader::normalize(contents);
   let mut modules = HashMap::new();
 
   // do an initial pass through, initialising all Conjunction modules as having empty inputs
@@ -153,6 +150,10 @@ fn read_file() -> HashMap<String, Module> {
   modules
 }
 
+fn read_file() -> HashMap<String, Module> {
+  parse_input(&reader::read(2023, 20))
+}
+
 // simulates a single button press (which sends a "low" input to the broadcast module).
 // Returns the total number of both low and high pulses sent (as that's what we need for part 1).
 // For part 2, it takes an optional argument of a particular module name to watch for when a low pulse
@@ -194,22 +195,33 @@ fn press_button(modules: &mut HashMap<String, Module>, module_to_watch: Option<&
   (low_count, high_count, false)
 }
 
-fn solve_part_1(modules: &mut HashMap<String, Module>) -> u64 {
+// how many times the button is pushed in part 1
+pub const BUTTON_PRESSES: usize = 1000;
+
+fn solve_part_1(modules: &mut HashMap<String, Module>, button_presses: usize) -> u64 {
   let mut low_total = 0;
   let mut high_total = 0;
 
-  for _ in 0..1000 {
+  for _ in 0..button_presses {
     let (low, high, _) = press_button(modules, None);
     low_total += low;
     high_total += high;
   }
+  trace::emit(Level::Summary, "pulse counts", &[("presses", &button_presses), ("low", &low_total), ("high", &high_total)], || {
+    format!("{} button presses send {} low pulses and {} high pulses", button_presses, low_total, high_total)
+  });
 
   low_total * high_total
 }
 
 pub fn part_1() -> u64 {
   let mut modules = read_file();
-  solve_part_1(&mut modules)
+  solve_part_1(&mut modules, BUTTON_PRESSES)
+}
+
+pub fn part_1_from_input(input: &str, button_presses: usize) -> u64 {
+  let mut modules = parse_input(input);
+  solve_part_1(&mut modules, button_presses)
 }
 
 // for part 2, we just have to notice that there are 4 different, independent parts of the set of modules.
@@ -261,6 +273,9 @@ fn solve_part_2(modules: &mut HashMap<String, Module>) -> u64 {
   for start in start_modules {
     let mut current_subsystem = vec![];
     let final_module = get_subtrees(modules, String::from(start), Some(String::from("broadcaster")), &mut current_subsystem).unwrap();
+    trace::emit(Level::Detail, "subsystem", &[("start", start), ("end", &final_module), ("size", &current_subsystem.len())], || {
+      format!("the broadcaster's output {} starts a subsystem of {} modules: {}", start, current_subsystem.len(), current_subsystem.join(", "))
+    });
     // also need a fresh copy of the modules (in original state) for each time we run through it!
     final_modules.push((final_module, modules.clone()));
   }
@@ -274,8 +289,14 @@ fn solve_part_2(modules: &mut HashMap<String, Module>) -> u64 {
 
   for (final_module, mut clean_modules) in final_modules {
     let wait_time = wait_for_low_pulse(&mut clean_modules, final_module.clone());
+    trace::emit(Level::Summary, "subsystem cycle", &[("module", &final_module), ("cycle_length", &wait_time)], || {
+      format!("the subsystem ending at {} first sends a low pulse after {} presses", final_module, wait_time)
+    });
     result = lcm(result, wait_time);
   }
+  trace::emit(Level::Summary, "combined cycle", &[("presses", &result)], || {
+    format!("so all of them first line up, sending a low pulse to rx, after {} presses (the LCM)", result)
+  });
   result
 }
 
@@ -283,3 +304,8 @@ pub fn part_2() -> u64 {
   let mut modules = read_file();
   solve_part_2(&mut modules)
 }
+
+pub fn part_2_from_input(input: &str) -> u64 {
+  let mut modules = parse_input(input);
+  solve_part_2(&mut modules)
+}
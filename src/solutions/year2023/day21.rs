@@ -1,6 +1,7 @@
+use crate::solutions::assumption_failed;
+use crate::trace::{self, Level};
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::prelude::*;
+use crate::solutions::reader;
 
 enum Space {
   Start,
@@ -8,11 +9,8 @@ enum Space {
   Rock,
 }
 
-fn read_file() -> Vec<Vec<Space>> {
-  let mut file = File::open("./input/input21.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
-
+fn parse_input(contents: &str) -> Vec<Vec<Space>> {
+  let contents: &str = &reader::normalize(contents);
   contents.lines().map(|line| {
     line.chars().map(|c| match c {
       'S' => Space::Start,
@@ -23,6 +21,10 @@ fn read_file() -> Vec<Vec<Space>> {
   }).collect()
 }
 
+fn read_file() -> Vec<Vec<Space>> {
+  parse_input(&reader::read(2023, 21))
+}
+
 fn find_start(grid: &Vec<Vec<Space>>) -> (usize, usize) {
   for (i, row) in grid.iter().enumerate() {
     for (j, space) in row.iter().enumerate() {
@@ -75,26 +77,43 @@ fn get_spaces_after_steps_from_start(grid: &Vec<Vec<Space>>, num_steps: usize) -
   get_spaces_after_steps(grid, start_space, num_steps)
 }
 
-fn solve_part_1(grid: &Vec<Vec<Space>>) -> usize {
-  get_spaces_after_steps_from_start(grid, 64).len()
+// the number of steps the elf takes in each part
+pub const PART_1_STEPS: usize = 64;
+pub const PART_2_STEPS: u64 = 26501365;
+
+fn solve_part_1(grid: &Vec<Vec<Space>>, num_steps: usize) -> usize {
+  let reachable = get_spaces_after_steps_from_start(grid, num_steps).len();
+  trace::emit(Level::Summary, "reachable", &[("steps", &num_steps), ("spaces", &reachable)], || {
+    format!("{} garden plots can be reached in exactly {} steps", reachable, num_steps)
+  });
+  reachable
 }
 
 pub fn part_1() -> usize {
   let grid = read_file();
-  solve_part_1(&grid)
+  solve_part_1(&grid, PART_1_STEPS)
+}
+
+pub fn part_1_from_input(input: &str, num_steps: usize) -> usize {
+  let grid = parse_input(input);
+  solve_part_1(&grid, num_steps)
 }
 
 // note this solution relies on some "nice features" of the grid in the input data, namely that
 // all edges are completely clear of rocks, as are the straight horizontal and vertical lines
-// through the centre (which is where the start space is located)
-fn solve_part_2(grid: &Vec<Vec<Space>>) -> u64 {
-  let num_steps = 26501365;
+// through the centre (which is where the start space is located).
+// It also relies on the number of steps being a whole number of grids plus the distance from the centre to an edge,
+// as 26501365 is for the real 131x131 grid.
+fn solve_part_2(grid: &Vec<Vec<Space>>, num_steps: u64) -> u64 {
   // note that grid width and grid height are the same
   let grid_size = grid.len();
   let n = num_steps / grid_size as u64;
-  // just hard-code this - it's both the distance remaining after going n * 131 to reach one of the furthest grid centres,
+  // this is both the distance remaining after going n * 131 to reach one of the furthest grid centres,
   // AND the distance from the centre of the grid to the entre of an edge, or from an edge centre to a corner
-  let distance_remaining = 65;
+  let distance_remaining = (grid_size - 1) / 2;
+  if num_steps % grid_size as u64 != distance_remaining as u64 {
+    assumption_failed(&format!("{} steps isn't a whole number of grids plus {}", num_steps, distance_remaining));
+  }
   // the diagram looks something like this, drawn for the very simple case where n = 2. Each letter marks a copy of the whole
   // grid. The starting grid is the I in the centre.
   //
@@ -166,6 +185,12 @@ fn solve_part_2(grid: &Vec<Vec<Space>>) -> u64 {
   let even_spaces_count = get_spaces_after_steps_from_start(grid, 2 * grid_size).len() as u64;
 
   let interior_count = odd_spaces_count * num_odd_interior_grids + even_spaces_count * num_even_interior_grids;
+  trace::emit(Level::Summary, "interior grids", &[("n", &n), ("odd_grids", &num_odd_interior_grids), ("even_grids", &num_even_interior_grids), ("count", &interior_count)], || {
+    format!(
+      "the steps reach {} whole grids in each direction. Inside that (the I's): {} odd grids with {} plots each and {} even grids with {} each - {} in total",
+      n, num_odd_interior_grids, odd_spaces_count, num_even_interior_grids, even_spaces_count, interior_count
+    )
+  });
 
   // now the X's
   let edge_center = (grid_size - 1) / 2;
@@ -180,6 +205,15 @@ fn solve_part_2(grid: &Vec<Vec<Space>>) -> u64 {
 
   let edge_count = ((n as usize - 1) * (top_and_left.count() + top_and_right.count() + bottom_and_left.count() + bottom_and_right.count())
                         + (reachable_from_top.len() + reachable_from_bottom.len() + reachable_from_left.len() + reachable_from_right.len())) as u64;
+  trace::emit(Level::Summary, "edge grids", &[("grids", &(4 * n)), ("count", &edge_count)], || {
+    format!("the {} partly-reached grids around the edge (the X's) have {} plots between them", 4 * n, edge_count)
+  });
+  trace::emit(Level::Detail, "edge entry points", &[("top", &reachable_from_top.len()), ("bottom", &reachable_from_bottom.len()), ("left", &reachable_from_left.len()), ("right", &reachable_from_right.len())], || {
+    format!(
+      "entering from the middle of an edge with {} steps left reaches {} (top), {} (bottom), {} (left) or {} (right) plots",
+      2 * distance_remaining, reachable_from_top.len(), reachable_from_bottom.len(), reachable_from_left.len(), reachable_from_right.len()
+    )
+  });
 
   // now the O's
   let top_left_corner_count = get_spaces_after_steps(grid, (0, 0), distance_remaining - 1).len();
@@ -188,6 +222,15 @@ fn solve_part_2(grid: &Vec<Vec<Space>>) -> u64 {
   let bottom_right_corner_count = get_spaces_after_steps(grid, (grid_size - 1, grid_size - 1), distance_remaining - 1).len();
 
   let beyond_edge_count = n * (top_left_corner_count + top_right_corner_count + bottom_left_corner_count + bottom_right_corner_count) as u64;
+  trace::emit(Level::Summary, "corner grids", &[("grids", &(4 * n)), ("count", &beyond_edge_count)], || {
+    format!("the {} grids only reached at a corner (the O's) have {} plots between them", 4 * n, beyond_edge_count)
+  });
+  trace::emit(Level::Detail, "corner entry points", &[("top_left", &top_left_corner_count), ("top_right", &top_right_corner_count), ("bottom_left", &bottom_left_corner_count), ("bottom_right", &bottom_right_corner_count)], || {
+    format!(
+      "entering at a corner with {} steps left reaches {} (top left), {} (top right), {} (bottom left) or {} (bottom right) plots",
+      distance_remaining - 1, top_left_corner_count, top_right_corner_count, bottom_left_corner_count, bottom_right_corner_count
+    )
+  });
 
   // add all together to get the final total
   interior_count + edge_count + beyond_edge_count
@@ -195,5 +238,10 @@ fn solve_part_2(grid: &Vec<Vec<Space>>) -> u64 {
 
 pub fn part_2() -> u64 {
   let grid = read_file();
-  solve_part_2(&grid)
+  solve_part_2(&grid, PART_2_STEPS)
+}
+
+pub fn part_2_from_input(input: &str, num_steps: u64) -> u64 {
+  let grid = parse_input(input);
+  solve_part_2(&grid, num_steps)
 }
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::prelude::*;
+use crate::profile;
+use crate::solutions::reader;
 
 #[derive(Clone, PartialEq, Eq)]
 struct Brick {
@@ -33,6 +33,7 @@ impl AllSpace {
   // in principle matter - however when done repeatly in the settle_all_bricks method below,
   // it becomes reliable.
   fn fall_all_bricks(&mut self) -> Vec<(Brick, Brick)> {
+    profile::count("fall passes");
     let mut occupied = self.get_occupied_spaces();
     let mut fallen_bricks = vec![];
     for brick in &mut self.bricks {
@@ -83,6 +84,7 @@ impl AllSpace {
 
   fn disintegratable_bricks(&self) -> Vec<&Brick> {
     self.bricks.iter().filter(|brick| {
+      profile::count("space clones");
       let mut copy = self.clone();
       copy.bricks = copy.bricks.iter().filter(|b| b != brick).map(|b| b.to_owned()).collect();
       copy.fall_all_bricks().len() == 0
@@ -110,16 +112,17 @@ fn parse_brick(line: &str) -> Brick {
   Brick { spaces }
 }
 
-fn read_file() -> AllSpace {
-  let mut file = File::open("./input/input22.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
-
+fn parse_input(contents: &str) -> AllSpace {
+  let contents: &str = &reader::normalize(contents);
   let bricks = contents.lines().map(parse_brick).collect();
 
   AllSpace { bricks }
 }
 
+fn read_file() -> AllSpace {
+  parse_input(&reader::read(2023, 22))
+}
+
 fn solve_part_1(space: &mut AllSpace) -> usize {
   space.settle_all_bricks();
   space.disintegratable_bricks().len()
@@ -130,13 +133,25 @@ pub fn part_1() -> usize {
   solve_part_1(&mut space)
 }
 
+pub fn part_1_from_input(input: &str) -> usize {
+  let mut space = parse_input(input);
+  solve_part_1(&mut space)
+}
+
 fn solve_part_2(space: &mut AllSpace) -> u32 {
+  // sort by increasing z-value as that will make everything easier!
+  // (The answer I get without it is wrong, while with it it's right - I'm not entirely sure why)
+  space.bricks.sort_by(
+    |Brick { spaces: spaces1 }, Brick { spaces: spaces2}|
+    spaces1[0].2.cmp(&spaces2[0].2)
+  );
   space.settle_all_bricks();
   let mut result = 0;
   for brick in &space.bricks {
     // need to take a copy so that we can keep reverting to this "settled state".
     // Unfortunately this has to be done inside the loop so that each brick can revert
     // to this.
+    profile::count("space clones");
     let mut settled_state = space.clone();
     settled_state.bricks = settled_state.bricks.iter().filter(|&b| b != brick).map(|b| b.to_owned()).collect();
     let mut fall_count = 0;
@@ -159,11 +174,10 @@ fn solve_part_2(space: &mut AllSpace) -> u32 {
 
 pub fn part_2() -> u32 {
   let mut space = read_file();
-  // sort by increasing z-value as that will make everything easier!
-  // (The answer I get without it is wrong, while with it it's right - I'm not entirely sure why)
-  space.bricks.sort_by(
-    |Brick { spaces: spaces1 }, Brick { spaces: spaces2}|
-    spaces1[0].2.cmp(&spaces2[0].2)
-  );
+  solve_part_2(&mut space)
+}
+
+pub fn part_2_from_input(input: &str) -> u32 {
+  let mut space = parse_input(input);
   solve_part_2(&mut space)
 }
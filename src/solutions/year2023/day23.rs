@@ -1,6 +1,6 @@
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::prelude::*;
+use crate::profile;
+use crate::solutions::reader;
 
 enum Direction {
   North,
@@ -17,11 +17,8 @@ enum Space {
   Slope(Direction),
 }
 
-fn read_file() -> Vec<Vec<Space>> {
-  let mut file = File::open("./input/input23.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
-
+fn parse_input(contents: &str) -> Vec<Vec<Space>> {
+  let contents: &str = &reader::normalize(contents);
   let mut map = vec![];
   let num_lines = contents.lines().count();
 
@@ -52,6 +49,10 @@ fn read_file() -> Vec<Vec<Space>> {
   map
 }
 
+fn read_file() -> Vec<Vec<Space>> {
+  parse_input(&reader::read(2023, 23))
+}
+
 // going to transform the data to a simpler form first
 
 struct GraphNode {
@@ -167,6 +168,7 @@ fn transform_data(map: &Vec<Vec<Space>>) -> HashMap<(usize, usize), GraphNode> {
 
 // recursive function that does the main work for finding the longest route on the graph representation
 fn find_all_routes(graph: &HashMap<(usize, usize), GraphNode>, start_row: usize, start_col: usize, current_route: Vec<(usize, usize)>) -> Vec<u32> {
+  profile::count("nodes expanded");
   // abandon if we've already been here!
   if current_route.contains(&(start_row, start_col)) {
     return vec![];
@@ -181,6 +183,8 @@ fn find_all_routes(graph: &HashMap<(usize, usize), GraphNode>, start_row: usize,
       distances_found.push(*distance);
     } else {
       // recursive find all routes from here, and add on the distance from the current start
+      profile::count("route clones");
+      profile::add("route steps cloned", current_route.len() as u64);
       let mut new_route = current_route.clone();
       new_route.push((start_row, start_col));
       let routes_from_here = find_all_routes(graph, *row, *col, new_route);
@@ -204,8 +208,13 @@ pub fn part_1() -> u32 {
   solve_part_1(&graph)
 }
 
-pub fn part_2() -> u32 {
-  let mut map = read_file();
+pub fn part_1_from_input(input: &str) -> u32 {
+  let map = parse_input(input);
+  let graph = transform_data(&map);
+  solve_part_1(&graph)
+}
+
+fn solve_part_2(mut map: Vec<Vec<Space>>) -> u32 {
   // need to replace all slopes with plain path:
   for row in &mut map {
     for space in row {
@@ -218,3 +227,13 @@ pub fn part_2() -> u32 {
   let graph = transform_data(&map);
   solve_part_1(&graph)
 }
+
+pub fn part_2() -> u32 {
+  let map = read_file();
+  solve_part_2(map)
+}
+
+pub fn part_2_from_input(input: &str) -> u32 {
+  let map = parse_input(input);
+  solve_part_2(map)
+}
@@ -1,7 +1,7 @@
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::prelude::*;
 use divisors;
+use crate::solutions::checked;
+use crate::solutions::reader;
 
 struct HailStone {
   x_position: i64,
@@ -27,14 +27,15 @@ fn read_line(line: &str) -> HailStone {
   }
 }
 
-fn read_file() -> Vec<HailStone> {
-  let mut file = File::open("./input/input24.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
-
+fn parse_input(contents: &str) -> Vec<HailStone> {
+  let contents: &str = &reader::normalize(contents);
   contents.lines().map(read_line).collect()
 }
 
+fn read_file() -> Vec<HailStone> {
+  parse_input(&reader::read(2023, 24))
+}
+
 // as the puzzle says, at least for now we only need to consider intersections in the x, y plane
 fn future_x_y_intersection_point(hailstone1: &HailStone, hailstone2: &HailStone) -> Option<(f64, f64)> {
   // if hailstone 1 has current position (px1, py1) and velocity (vx1, vy1), and the same for hailstone 2
@@ -61,7 +62,7 @@ fn future_x_y_intersection_point(hailstone1: &HailStone, hailstone2: &HailStone)
   // - except it probably won't because of floating-point errors, but it will still give nonsensical results so we
   // need to test for it specifically. There will of course be no intersection in this case, so:
 
-  if hailstone1.x_velocity * hailstone2.y_velocity == hailstone1.y_velocity * hailstone2.x_velocity {
+  if checked!(mul, hailstone1.x_velocity, hailstone2.y_velocity) == checked!(mul, hailstone1.y_velocity, hailstone2.x_velocity) {
     return None;
   }
 
@@ -91,9 +92,11 @@ fn future_x_y_intersection_point(hailstone1: &HailStone, hailstone2: &HailStone)
   Some((x, y))
 }
 
-fn solve_part_1(hailstones: &Vec<HailStone>) -> u32 {
-  let min_value = 200000000000000f64;
-  let max_value = 400000000000000f64;
+// the bounds of the test area in part 1, in both the x and y directions
+pub const TEST_AREA_MIN: f64 = 200000000000000f64;
+pub const TEST_AREA_MAX: f64 = 400000000000000f64;
+
+fn solve_part_1(hailstones: &Vec<HailStone>, min_value: f64, max_value: f64) -> u32 {
   let mut intersections_to_count = 0;
 
   for i in 0..hailstones.len() {
@@ -114,7 +117,12 @@ fn solve_part_1(hailstones: &Vec<HailStone>) -> u32 {
 
 pub fn part_1() -> u32 {
   let hailstones = read_file();
-  solve_part_1(&hailstones)
+  solve_part_1(&hailstones, TEST_AREA_MIN, TEST_AREA_MAX)
+}
+
+pub fn part_1_from_input(input: &str, min_value: f64, max_value: f64) -> u32 {
+  let hailstones = parse_input(input);
+  solve_part_1(&hailstones, min_value, max_value)
 }
 
 fn solve_part_2(hailstones: &Vec<HailStone>) -> i64 {
@@ -281,33 +289,43 @@ fn solve_part_2(hailstones: &Vec<HailStone>) -> i64 {
   // (the second time of intersection being the only new unknown). So we can easily solve this with linear
   // algebra.
   // I'll spare the details of the algebra in this comment but leave the computations to work out the answer:
-  let hailstone_1_time_coeff_x = hailstones[0].x_velocity - x_velocity;
+  let hailstone_1_time_coeff_x = checked!(sub, hailstones[0].x_velocity, x_velocity);
   let hailstone_1_const_x = hailstones[0].x_position;
-  let hailstone_1_time_coeff_y = hailstones[0].y_velocity - y_velocity;
+  let hailstone_1_time_coeff_y = checked!(sub, hailstones[0].y_velocity, y_velocity);
   let hailstone_1_const_y = hailstones[0].y_position;
-  let hailstone_1_time_coeff_z = hailstones[0].z_velocity - z_velocity;
+  let hailstone_1_time_coeff_z = checked!(sub, hailstones[0].z_velocity, z_velocity);
   let hailstone_1_const_z = hailstones[0].z_position;
 
-  let hailstone_2_time_coeff_x = hailstones[1].x_velocity - x_velocity;
+  let hailstone_2_time_coeff_x = checked!(sub, hailstones[1].x_velocity, x_velocity);
   let hailstone_2_const_x = hailstones[1].x_position;
-  let hailstone_2_time_coeff_y = hailstones[1].y_velocity - y_velocity;
+  let hailstone_2_time_coeff_y = checked!(sub, hailstones[1].y_velocity, y_velocity);
   let hailstone_2_const_y = hailstones[1].y_position;
- 
-  let hailstone_1_time_numerator = hailstone_2_time_coeff_y * (hailstone_2_const_x - hailstone_1_const_x)
-                                  + hailstone_2_time_coeff_x * (hailstone_1_const_y - hailstone_2_const_y);
-  
-  let hailstone_1_time_denominator = hailstone_1_time_coeff_x * hailstone_2_time_coeff_y - hailstone_1_time_coeff_y * hailstone_2_time_coeff_x;
+
+  // these products are of positions (in the hundreds of trillions) with velocities, so are the likeliest
+  // place for anything to overflow
+  let x_difference = checked!(sub, hailstone_2_const_x, hailstone_1_const_x);
+  let y_difference = checked!(sub, hailstone_1_const_y, hailstone_2_const_y);
+  let hailstone_1_time_numerator = checked!(add, checked!(mul, hailstone_2_time_coeff_y, x_difference),
+                                                 checked!(mul, hailstone_2_time_coeff_x, y_difference));
+
+  let hailstone_1_time_denominator = checked!(sub, checked!(mul, hailstone_1_time_coeff_x, hailstone_2_time_coeff_y),
+                                                   checked!(mul, hailstone_1_time_coeff_y, hailstone_2_time_coeff_x));
   assert!(hailstone_1_time_numerator % hailstone_1_time_denominator == 0);
   let hailstone_1_time = hailstone_1_time_numerator / hailstone_1_time_denominator;
 
-  let x_position = hailstone_1_time_coeff_x * hailstone_1_time + hailstone_1_const_x;
-  let y_position = hailstone_1_time_coeff_y * hailstone_1_time + hailstone_1_const_y;
-  let z_position = hailstone_1_time_coeff_z * hailstone_1_time + hailstone_1_const_z;
+  let x_position = checked!(add, checked!(mul, hailstone_1_time_coeff_x, hailstone_1_time), hailstone_1_const_x);
+  let y_position = checked!(add, checked!(mul, hailstone_1_time_coeff_y, hailstone_1_time), hailstone_1_const_y);
+  let z_position = checked!(add, checked!(mul, hailstone_1_time_coeff_z, hailstone_1_time), hailstone_1_const_z);
 
-  x_position + y_position + z_position
+  checked!(add, checked!(add, x_position, y_position), z_position)
 }
 
 pub fn part_2() -> i64 {
   let hailstones = read_file();
   solve_part_2(&hailstones)
 }
+
+pub fn part_2_from_input(input: &str) -> i64 {
+  let hailstones = parse_input(input);
+  solve_part_2(&hailstones)
+}
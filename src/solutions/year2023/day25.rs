@@ -1,6 +1,5 @@
-use std::fs::File;
-use std::io::prelude::*;
 use rand;
+use crate::solutions::reader;
 
 #[derive(Clone)]
 struct Edge {
@@ -13,11 +12,8 @@ struct Graph {
   vertices: Vec<String>,
 }
 
-fn read_file() -> Graph {
-  let mut file = File::open("./input/input25.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
-
+fn parse_input(contents: &str) -> Graph {
+  let contents: &str = &reader::normalize(contents);
   let mut vertices = vec![];
   let mut edges = vec![];
 
@@ -42,6 +38,10 @@ fn read_file() -> Graph {
   Graph { vertices, edges }
 }
 
+fn read_file() -> Graph {
+  parse_input(&reader::read(2023, 25))
+}
+
 // going to use Karger's algorithm - https://en.wikipedia.org/wiki/Karger%27s_algorithm - to solve this.
 // It isn't actually guaranteed to work (but has high probability), so will need to loop over various attempts until we
 // succeed in reducing the graph to 2 vertices with 3 edges remaining between them.
@@ -101,3 +101,8 @@ pub fn part_1() -> u32 {
   let mut graph = read_file();
   solve_part_1(&mut graph)
 }
+
+pub fn part_1_from_input(input: &str) -> u32 {
+  let mut graph = parse_input(input);
+  solve_part_1(&mut graph)
+}
@@ -0,0 +1,595 @@
+use std::str::FromStr;
+use crate::solutions::reader;
+use crate::trace::{self, Level};
+
+pub enum EngineEntity {
+  PartNumber(u32),
+  Symbol(char),
+}
+
+pub struct PositionedEntity {
+  entity: EngineEntity,
+  // of course the positions cannot be negative, but when computing possible neighbours it's easier to
+  // allow -1 values
+  position: (isize, isize),
+}
+
+impl PositionedEntity {
+  // how many cells of the schematic it takes up
+  fn length(&self) -> isize {
+    match self.entity {
+      EngineEntity::PartNumber(num) => num.to_string().len() as isize,
+      EngineEntity::Symbol(_) => 1,
+    }
+  }
+}
+
+// Which entity (by its index in the list) is in each cell of the schematic, so that finding what's next to
+// something only means looking at the cells around it, rather than through every other entity.
+struct SchematicIndex {
+  width: isize,
+  height: isize,
+  cells: Vec<Option<usize>>,
+}
+
+impl SchematicIndex {
+  fn new(engine: &[PositionedEntity]) -> SchematicIndex {
+    let height = engine.iter().map(|entity| entity.position.0 + 1).max().unwrap_or(0);
+    let width = engine.iter().map(|entity| entity.position.1 + entity.length()).max().unwrap_or(0);
+    let mut cells = vec![None; (width * height) as usize];
+    for (id, entity) in engine.iter().enumerate() {
+      let (row, col) = entity.position;
+      for i in 0..entity.length() {
+        cells[(row * width + col + i) as usize] = Some(id);
+      }
+    }
+    SchematicIndex { width, height, cells }
+  }
+
+  fn at(&self, (row, col): (isize, isize)) -> Option<usize> {
+    if row < 0 || col < 0 || row >= self.height || col >= self.width {
+      return None;
+    }
+    self.cells[(row * self.width + col) as usize]
+  }
+
+  // Every other entity in the cells around this one, each just once even if it touches in several places. Without
+  // diagonals, only what's directly above, below or to either side counts.
+  fn neighbours(&self, engine: &[PositionedEntity], id: usize, diagonal: bool) -> Vec<usize> {
+    let (row, col) = engine[id].position;
+    let end = col + engine[id].length();
+    let mut neighbours = vec![];
+    for y in (row - 1)..=(row + 1) {
+      for x in (col - 1)..=end {
+        let is_corner = y != row && (x < col || x == end);
+        if is_corner && !diagonal {
+          continue;
+        }
+        if let Some(other) = self.at((y, x)) {
+          if other != id && !neighbours.contains(&other) {
+            neighbours.push(other);
+          }
+        }
+      }
+    }
+    neighbours
+  }
+}
+
+fn parse_input(contents: &str) -> Vec<PositionedEntity> {
+  let contents: &str = &reader::normalize(contents);
+  let mut engine = vec![];
+  for (row_index, line) in contents.lines().enumerate() {
+    let mut number_so_far: Option<u32> = None;
+    for (column_index, char) in line.chars().enumerate() {
+      let mut is_num_finished = column_index == line.len() - 1;
+      let mut finished_before_end = false;
+      match char.to_digit(10) {
+        Some(digit) => {
+          match number_so_far {
+            Some(num) => number_so_far = Some(num * 10 + digit),
+            None => number_so_far = Some(digit),
+          }
+        }
+        None => {
+          is_num_finished = number_so_far.is_some();
+          finished_before_end = true;
+          if char != '.' {
+            let entity = EngineEntity::Symbol(char);
+            engine.push(PositionedEntity { entity, position: (row_index as isize, column_index as isize) })
+          }
+        }
+      }
+      if is_num_finished {
+        let part_number = number_so_far.unwrap();
+        let part = EngineEntity::PartNumber(part_number);
+        let index_when_finished = if finished_before_end { column_index } else { column_index + 1 };
+        let position = (row_index as isize, (index_when_finished - part_number.to_string().len()) as isize);
+        engine.push(PositionedEntity {
+          entity: part,
+          position
+        });
+        number_so_far = None;
+      }
+    }
+  }
+  engine
+}
+
+fn read_file() -> Vec<PositionedEntity> {
+  parse_input(&reader::read(2023, 3))
+}
+
+// How to combine the numbers attached to a symbol into its value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Combine {
+  Product,
+  Sum,
+  Max,
+}
+
+// What one symbol needs to count for anything in part 2 (where the puzzle's only rule is for gears, which are a `*`
+// with exactly two numbers next to it, diagonally or not, multiplied together).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SymbolRule {
+  // exactly how many numbers must be next to it, if it matters
+  pub numbers: Option<usize>,
+  // whether a number touching it only at a corner is next to it
+  pub diagonal: bool,
+  pub combine: Combine,
+}
+
+// The rules for each symbol that has one, written like "*=2,diagonal,product;#=any,orthogonal,sum". Anything left
+// out of a rule is any number of numbers, diagonals counting, and multiplying. A symbol without a rule still makes
+// the numbers next to it (diagonals included) part numbers, but has no value of its own.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SymbolRules(Vec<(char, SymbolRule)>);
+
+// the puzzle's own rules
+pub const SYMBOL_RULES: &str = "*=2,diagonal,product";
+
+impl SymbolRules {
+  fn get(&self, symbol: char) -> Option<&SymbolRule> {
+    self.0.iter().find(|(other, _)| *other == symbol).map(|(_, rule)| rule)
+  }
+}
+
+impl FromStr for SymbolRules {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut rules = vec![];
+    for text in s.split(';').filter(|text| !text.is_empty()) {
+      // the symbol is the first character whatever it is, so that `=` can have a rule too
+      let mut chars = text.chars();
+      let (Some(symbol), Some(settings)) = (chars.next(), chars.as_str().strip_prefix('=')) else {
+        return Err(format!("expected a single symbol and then = in {}", text));
+      };
+      let mut rule = SymbolRule { numbers: None, diagonal: true, combine: Combine::Product };
+      for setting in settings.split(',').filter(|setting| !setting.is_empty()) {
+        match setting {
+          "any" => rule.numbers = None,
+          "diagonal" => rule.diagonal = true,
+          "orthogonal" => rule.diagonal = false,
+          "product" => rule.combine = Combine::Product,
+          "sum" => rule.combine = Combine::Sum,
+          "max" => rule.combine = Combine::Max,
+          number => rule.numbers = Some(number.parse().map_err(|_| format!("unknown setting {} in the rule for {}", number, symbol))?),
+        }
+      }
+      rules.push((symbol, rule));
+    }
+    Ok(SymbolRules(rules))
+  }
+}
+
+// One symbol in the schematic, along with the part numbers next to it (under its rule) and what they come to - which
+// is nothing if it has no rule or they don't satisfy it.
+pub struct SymbolReport {
+  pub symbol: char,
+  pub position: (isize, isize),
+  pub part_numbers: Vec<u32>,
+  pub value: Option<u64>,
+}
+
+fn symbol_report(engine: &[PositionedEntity], index: &SchematicIndex, rules: &SymbolRules, id: usize) -> Option<SymbolReport> {
+  let EngineEntity::Symbol(symbol) = engine[id].entity else { return None };
+  let rule = rules.get(symbol);
+  let part_numbers: Vec<u32> = index.neighbours(engine, id, rule.is_none_or(|rule| rule.diagonal)).iter().filter_map(|&other| {
+    match engine[other].entity {
+      EngineEntity::PartNumber(n) => Some(n),
+      EngineEntity::Symbol(_) => None,
+    }
+  }).collect();
+  let value = rule.filter(|rule| !part_numbers.is_empty() && rule.numbers.is_none_or(|numbers| numbers == part_numbers.len())).map(|rule| {
+    let numbers = part_numbers.iter().map(|&n| n as u64);
+    match rule.combine {
+      Combine::Product => numbers.product(),
+      Combine::Sum => numbers.sum(),
+      Combine::Max => numbers.max().unwrap(),
+    }
+  });
+  Some(SymbolReport { symbol, position: engine[id].position, part_numbers, value })
+}
+
+// a report for every symbol in the schematic, in the order they appear
+pub fn symbol_reports(engine: &[PositionedEntity], rules: &SymbolRules) -> Vec<SymbolReport> {
+  let index = SchematicIndex::new(engine);
+  (0..engine.len()).filter_map(|id| symbol_report(engine, &index, rules, id)).collect()
+}
+
+fn solve_part_1(engine: Vec<PositionedEntity>, rules: &SymbolRules) -> u64 {
+  // a number is a part number if any symbol has it next to it - and it's only counted once however many do
+  let index = SchematicIndex::new(&engine);
+  let mut is_genuine_part = vec![false; engine.len()];
+  for id in 0..engine.len() {
+    if let EngineEntity::Symbol(symbol) = engine[id].entity {
+      for other in index.neighbours(&engine, id, rules.get(symbol).is_none_or(|rule| rule.diagonal)) {
+        is_genuine_part[other] = true;
+      }
+    }
+  }
+  engine.iter().zip(is_genuine_part).map(|(entity, is_genuine_part)| match entity.entity {
+    EngineEntity::PartNumber(num) if is_genuine_part => num as u64,
+    _ => 0,
+  }).sum()
+}
+
+pub fn part_1() -> u64 {
+  let engine = read_file();
+  solve_part_1(engine, &SYMBOL_RULES.parse().unwrap())
+}
+
+pub fn part_1_from_input(input: &str, rules: &SymbolRules) -> u64 {
+  let engine = parse_input(input);
+  solve_part_1(engine, rules)
+}
+
+fn solve_part_2(engine: Vec<PositionedEntity>, rules: &SymbolRules) -> u64 {
+  let mut ratio_sum = 0;
+  for report in symbol_reports(&engine, rules) {
+    if let Some(value) = report.value {
+      trace::emit(Level::Detail, "symbol", &[("symbol", &report.symbol), ("value", &value)], || {
+        let numbers: Vec<String> = report.part_numbers.iter().map(|n| n.to_string()).collect();
+        format!("{} at row {}, column {} has {} next to it, giving {}", report.symbol, report.position.0, report.position.1, numbers.join(" and "), value)
+      });
+      ratio_sum += value;
+    }
+  }
+  ratio_sum
+}
+
+pub fn part_2() -> u64 {
+  let engine = read_file();
+  solve_part_2(engine, &SYMBOL_RULES.parse().unwrap())
+}
+
+pub fn part_2_from_input(input: &str, rules: &SymbolRules) -> u64 {
+  let engine = parse_input(input);
+  solve_part_2(engine, rules)
+}
+
+// What each entity turned out to be. A gear is any symbol whose rule it satisfies (so in the puzzle, a `*` next to
+// exactly two numbers), and its partners are the part numbers that make it one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Classification {
+  PartNumber,
+  GearPartner,
+  NotPartNumber,
+  Gear,
+  Symbol,
+}
+
+impl Classification {
+  fn name(&self) -> &'static str {
+    match self {
+      Classification::PartNumber => "part-number",
+      Classification::GearPartner => "gear-partner",
+      Classification::NotPartNumber => "not-part-number",
+      Classification::Gear => "gear",
+      Classification::Symbol => "symbol",
+    }
+  }
+}
+
+// every entity's classification, and the ids of the others it's next to (as far as the rules are concerned)
+fn classify(engine: &[PositionedEntity], rules: &SymbolRules) -> Vec<(Classification, Vec<usize>)> {
+  let index = SchematicIndex::new(engine);
+  let mut result: Vec<(Classification, Vec<usize>)> = engine.iter().map(|entity| match entity.entity {
+    EngineEntity::PartNumber(_) => (Classification::NotPartNumber, vec![]),
+    EngineEntity::Symbol(_) => (Classification::Symbol, vec![]),
+  }).collect();
+  for id in 0..engine.len() {
+    let Some(report) = symbol_report(engine, &index, rules, id) else { continue };
+    let EngineEntity::Symbol(symbol) = engine[id].entity else { continue };
+    let is_gear = report.value.is_some();
+    if is_gear {
+      result[id].0 = Classification::Gear;
+    }
+    let numbers: Vec<usize> = index.neighbours(engine, id, rules.get(symbol).is_none_or(|rule| rule.diagonal)).into_iter().filter(|&other| {
+      matches!(engine[other].entity, EngineEntity::PartNumber(_))
+    }).collect();
+    for &number in numbers.iter() {
+      result[number].1.push(id);
+      if is_gear {
+        result[number].0 = Classification::GearPartner;
+      } else if result[number].0 == Classification::NotPartNumber {
+        result[number].0 = Classification::PartNumber;
+      }
+    }
+    result[id].1 = numbers;
+  }
+  result
+}
+
+// How to show the annotated schematic: coloured for a terminal, with brackets round things for plain text, or as
+// a list of every entity, one per line with tab-separated fields, for other programs to read.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Annotation {
+  Ansi,
+  Brackets,
+  List,
+}
+
+impl FromStr for Annotation {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "ansi" => Ok(Annotation::Ansi),
+      "brackets" => Ok(Annotation::Brackets),
+      "list" => Ok(Annotation::List),
+      other => Err(format!("unknown annotation style: {} (expected ansi, brackets or list)", other)),
+    }
+  }
+}
+
+// what goes either side of an entity of each classification
+fn markup(classification: Classification, style: Annotation) -> (&'static str, &'static str) {
+  match (style, classification) {
+    (Annotation::Ansi, Classification::PartNumber) => ("\x1b[32m", "\x1b[0m"),
+    (Annotation::Ansi, Classification::GearPartner) => ("\x1b[36m", "\x1b[0m"),
+    (Annotation::Ansi, Classification::NotPartNumber) => ("\x1b[31m", "\x1b[0m"),
+    (Annotation::Ansi, Classification::Gear) => ("\x1b[1;33m", "\x1b[0m"),
+    (Annotation::Ansi, Classification::Symbol) => ("\x1b[1m", "\x1b[0m"),
+    (_, Classification::PartNumber) => ("[", "]"),
+    (_, Classification::GearPartner) => ("<", ">"),
+    (_, Classification::NotPartNumber) => ("(", ")"),
+    (_, Classification::Gear) => ("{", "}"),
+    (_, Classification::Symbol) => ("", ""),
+  }
+}
+
+fn list_entities(engine: &[PositionedEntity], classifications: &[(Classification, Vec<usize>)]) -> String {
+  let mut lines = vec![String::from("id\trow\tcolumn\tkind\ttext\tclassification\tnext_to")];
+  for (id, (entity, (classification, next_to))) in engine.iter().zip(classifications).enumerate() {
+    let (kind, text) = match entity.entity {
+      EngineEntity::PartNumber(n) => ("number", n.to_string()),
+      EngineEntity::Symbol(symbol) => ("symbol", symbol.to_string()),
+    };
+    let next_to: Vec<String> = next_to.iter().map(|other| other.to_string()).collect();
+    lines.push(format!("{}\t{}\t{}\t{}\t{}\t{}\t{}", id, entity.position.0, entity.position.1, kind, text, classification.name(), next_to.join(",")));
+  }
+  lines.join("\n")
+}
+
+// Entry point for the `schematic` command: the schematic again, with every number and symbol marked up to say what
+// it was taken to be - or the list of them all.
+pub fn annotate(input: &str, rules: &SymbolRules, style: Annotation) -> String {
+  let engine = parse_input(input);
+  let classifications = classify(&engine, rules);
+  if style == Annotation::List {
+    return list_entities(&engine, &classifications);
+  }
+  let index = SchematicIndex::new(&engine);
+  let mut lines = vec![];
+  for (row, line) in reader::normalize(input).lines().enumerate() {
+    let mut annotated = String::new();
+    for (col, char) in line.chars().enumerate() {
+      let (row, col) = (row as isize, col as isize);
+      let entity = index.at((row, col));
+      let (before, after) = entity.map_or(("", ""), |id| markup(classifications[id].0, style));
+      if entity.is_some_and(|id| engine[id].position == (row, col)) {
+        annotated.push_str(before);
+      }
+      annotated.push(char);
+      if entity.is_some_and(|id| engine[id].position.1 + engine[id].length() - 1 == col) {
+        annotated.push_str(after);
+      }
+    }
+    lines.push(annotated);
+  }
+  let key: Vec<String> = [
+    (Classification::PartNumber, "part number"),
+    (Classification::GearPartner, "gear partner"),
+    (Classification::NotPartNumber, "not a part number"),
+    (Classification::Gear, "gear"),
+  ].iter().map(|(classification, name)| {
+    let (before, after) = markup(*classification, style);
+    format!("{}{}{}", before, name, after)
+  }).collect();
+  lines.push(format!("\nkey: {}", key.join(", ")));
+  lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // the example from the puzzle
+  const EXAMPLE: &str = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..";
+
+  // numbers in the corners and against every edge, and symbols next to each other
+  const EDGES: &str = "12*34\n*...5\n6.**7\n89.10";
+
+  // A made-up schematic, the same every time for a given seed: mostly dots, with numbers of up to three digits and
+  // a scattering of symbols, some right next to each other. A simple generator keeps day 3 from needing rand.
+  fn random_schematic(seed: u64, rows: usize, columns: usize) -> String {
+    let mut state = seed;
+    let mut next = |below: u64| {
+      state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+      (state >> 33) % below
+    };
+    let mut lines = vec![];
+    for _ in 0..rows {
+      let mut line = String::new();
+      while line.len() < columns {
+        match next(10) {
+          0..=2 if !line.ends_with(|c: char| c.is_ascii_digit()) => {
+            for _ in 0..=next(3) {
+              line.push(char::from(b'0' + next(10) as u8));
+            }
+          },
+          3 | 4 => line.push("*#+$/@=%&-".chars().nth(next(10) as usize).unwrap()),
+          _ => line.push('.'),
+        }
+      }
+      line.truncate(columns);
+      lines.push(line);
+    }
+    lines.join("\n")
+  }
+
+  fn schematics() -> Vec<String> {
+    let mut schematics = vec![String::from(EXAMPLE), String::from(EDGES)];
+    schematics.extend((1..=20).map(|seed| random_schematic(seed, 30, 40)));
+    schematics
+  }
+
+  // whether two entities touch, worked out from their positions alone rather than with the index
+  fn touching(a: &PositionedEntity, b: &PositionedEntity, diagonal: bool) -> bool {
+    let (a_start, a_end) = (a.position.1, a.position.1 + a.length());
+    let (b_start, b_end) = (b.position.1, b.position.1 + b.length());
+    match (a.position.0 - b.position.0).abs() {
+      0 => a_end == b_start || b_end == a_start,
+      1 if diagonal => b_start <= a_end && a_start <= b_end,
+      1 => b_start < a_end && a_start < b_end,
+      _ => false,
+    }
+  }
+
+  fn brute_force_neighbours(engine: &[PositionedEntity], id: usize, diagonal: bool) -> Vec<usize> {
+    (0..engine.len()).filter(|&other| other != id && touching(&engine[id], &engine[other], diagonal)).collect()
+  }
+
+  #[test]
+  fn the_index_finds_the_same_neighbours_as_checking_every_pair() {
+    for schematic in schematics() {
+      let engine = parse_input(&schematic);
+      let index = SchematicIndex::new(&engine);
+      for id in 0..engine.len() {
+        for diagonal in [true, false] {
+          let mut neighbours = index.neighbours(&engine, id, diagonal);
+          neighbours.sort();
+          assert_eq!(neighbours, brute_force_neighbours(&engine, id, diagonal), "{} at {:?} in\n{}", id, engine[id].position, schematic);
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn the_index_gives_the_same_answers_as_checking_every_pair() {
+    let rules: SymbolRules = SYMBOL_RULES.parse().unwrap();
+    for schematic in schematics() {
+      let engine = parse_input(&schematic);
+      let mut part_numbers = 0;
+      let mut gear_ratios = 0;
+      for (id, entity) in engine.iter().enumerate() {
+        let neighbours = brute_force_neighbours(&engine, id, true);
+        let has_symbol = neighbours.iter().any(|&other| matches!(engine[other].entity, EngineEntity::Symbol(_)));
+        let numbers: Vec<u64> = neighbours.iter().filter_map(|&other| match engine[other].entity {
+          EngineEntity::PartNumber(n) => Some(n as u64),
+          EngineEntity::Symbol(_) => None,
+        }).collect();
+        match entity.entity {
+          EngineEntity::PartNumber(n) if has_symbol => part_numbers += n as u64,
+          EngineEntity::Symbol('*') if numbers.len() == 2 => gear_ratios += numbers[0] * numbers[1],
+          _ => {},
+        }
+      }
+      assert_eq!(part_1_from_input(&schematic, &rules), part_numbers, "part 1 of\n{}", schematic);
+      assert_eq!(part_2_from_input(&schematic, &rules), gear_ratios, "part 2 of\n{}", schematic);
+    }
+    assert_eq!(part_1_from_input(EXAMPLE, &rules), 4361);
+    assert_eq!(part_2_from_input(EXAMPLE, &rules), 467835);
+  }
+
+  // a * with numbers above and to either side of it, and another at a corner, and an = next to three numbers
+  const SYMBOLS: &str = "..5..\n.2*3.\n.7...\n4=6..";
+
+  fn reports(rules: &str) -> Vec<(char, Vec<u32>, Option<u64>)> {
+    let engine = parse_input(SYMBOLS);
+    symbol_reports(&engine, &rules.parse().unwrap()).into_iter().map(|report| (report.symbol, report.part_numbers, report.value)).collect()
+  }
+
+  #[test]
+  fn symbol_rules_are_parsed() {
+    let rule = |numbers, diagonal, combine| SymbolRule { numbers, diagonal, combine };
+    assert_eq!(SYMBOL_RULES.parse(), Ok(SymbolRules(vec![('*', rule(Some(2), true, Combine::Product))])));
+    assert_eq!("#=any,orthogonal,sum;+=;==3,max".parse(), Ok(SymbolRules(vec![
+      ('#', rule(None, false, Combine::Sum)),
+      ('+', rule(None, true, Combine::Product)),
+      ('=', rule(Some(3), true, Combine::Max)),
+    ])));
+    assert_eq!("".parse(), Ok(SymbolRules(vec![])));
+    for bad in ["*", "**=2", "=2", "*=two", "*=2;#"] {
+      assert!(bad.parse::<SymbolRules>().is_err(), "{}", bad);
+    }
+  }
+
+  #[test]
+  fn corners_only_count_with_diagonals() {
+    assert_eq!(reports("*=any,orthogonal")[0].1, [5, 2, 3]);
+    assert_eq!(reports("*=any,diagonal")[0].1, [5, 2, 3, 7]);
+    assert_eq!(part_1_from_input("1.\n.#", &"#=orthogonal".parse().unwrap()), 0);
+    assert_eq!(part_1_from_input("1.\n.#", &"#=diagonal".parse().unwrap()), 1);
+  }
+
+  #[test]
+  fn symbols_combine_their_numbers_by_their_rules() {
+    assert_eq!(reports("*=3,orthogonal,sum"), [('*', vec![5, 2, 3], Some(10)), ('=', vec![7, 4, 6], None)]);
+    assert_eq!(reports("*=4,max;==3"), [('*', vec![5, 2, 3, 7], Some(7)), ('=', vec![7, 4, 6], Some(168))]);
+    assert_eq!(reports("*=any"), [('*', vec![5, 2, 3, 7], Some(210)), ('=', vec![7, 4, 6], None)]);
+    // the wrong number of numbers means no value at all
+    assert_eq!(reports("*=2;==2"), [('*', vec![5, 2, 3, 7], None), ('=', vec![7, 4, 6], None)]);
+    assert_eq!(part_2_from_input(SYMBOLS, &"*=3,orthogonal,sum;==3,sum".parse().unwrap()), 10 + 17);
+  }
+
+  #[test]
+  fn entities_are_classified_by_what_they_touch() {
+    let listing = annotate("12*.4\n#.3..", &SYMBOL_RULES.parse().unwrap(), Annotation::List);
+    assert_eq!(listing.lines().collect::<Vec<_>>(), [
+      "id\trow\tcolumn\tkind\ttext\tclassification\tnext_to",
+      "0\t0\t2\tsymbol\t*\tgear\t1,4",
+      "1\t0\t0\tnumber\t12\tgear-partner\t0,3",
+      "2\t0\t4\tnumber\t4\tnot-part-number\t",
+      "3\t1\t0\tsymbol\t#\tsymbol\t1",
+      "4\t1\t2\tnumber\t3\tgear-partner\t0",
+    ]);
+    // with a rule for the # but not the *, the # is the gear and the * just a symbol
+    let classifications = classify(&parse_input("12*.4\n#.3.."), &"#=any,sum".parse().unwrap());
+    let names: Vec<&str> = classifications.iter().map(|(classification, _)| classification.name()).collect();
+    assert_eq!(names, ["symbol", "gear-partner", "not-part-number", "gear", "part-number"]);
+  }
+
+  #[test]
+  fn the_schematic_is_marked_up() {
+    let annotated = annotate(EXAMPLE, &SYMBOL_RULES.parse().unwrap(), Annotation::Brackets);
+    assert_eq!(annotated.lines().collect::<Vec<_>>(), [
+      "<467>..(114)..",
+      "...{*}......",
+      "..<35>..[633].",
+      "......#...",
+      "[617]*......",
+      ".....+.(58).",
+      "..[592].....",
+      "......<755>.",
+      "...$.{*}....",
+      ".[664].<598>..",
+      "",
+      "key: [part number], <gear partner>, (not a part number), {gear}",
+    ]);
+    let annotated = annotate("12*.4", &SYMBOL_RULES.parse().unwrap(), Annotation::Ansi);
+    assert_eq!(annotated.lines().next(), Some("\x1b[32m12\x1b[0m\x1b[1m*\x1b[0m.\x1b[31m4\x1b[0m"));
+  }
+}
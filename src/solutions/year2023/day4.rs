@@ -0,0 +1,233 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+use num::{BigUint, One, Zero};
+use crate::solutions::reader;
+use crate::trace::{self, Level};
+
+pub struct Card {
+  winning: HashSet<u32>,
+  actual: HashSet<u32>,
+}
+
+impl Card {
+  // how many of the numbers on the card are winning ones
+  fn matches(&self) -> usize {
+    self.winning.intersection(&self.actual).count()
+  }
+}
+
+fn read_line(line: &str) -> Card {
+  let parts: Vec<&str> = line.split(" | ").collect();
+  let winning_str = parts[0].split(": ").collect::<Vec<&str>>()[1];
+  let actual_str = parts[1];
+  let mut winning = HashSet::new();
+  let mut actual = HashSet::new();
+  for num_part in winning_str.split_whitespace() {
+    winning.insert(num_part.parse().unwrap());
+  }
+  for num_part in actual_str.split_whitespace() {
+    actual.insert(num_part.parse().unwrap());
+  }
+  
+  Card {
+    winning,
+    actual,
+  }
+}
+
+fn parse_input(contents: &str) -> Vec<Card> {
+  let contents: &str = &reader::normalize(contents);
+  contents.lines().map(read_line).collect()
+}
+
+fn read_file() -> Vec<Card> {
+  parse_input(&reader::read(2023, 4))
+}
+
+fn solve_part_1(cards: Vec<Card>) -> u32 {
+  cards.iter().map(|card| {
+    let number_of_winners = card.matches();
+    if number_of_winners == 0 {
+      return 0;
+    }
+    return 2u32.pow(number_of_winners as u32 - 1)
+  }).sum() 
+}
+
+pub fn part_1() -> u32 {
+  let cards = read_file();
+  solve_part_1(cards)
+}
+
+pub fn part_1_from_input(input: &str) -> u32 {
+  let cards = parse_input(input);
+  solve_part_1(cards)
+}
+
+// Which cards a card wins copies of, and how many copies any card can have, written like "skip=0,step=1,cap=1000".
+// A card with n matches wins one copy (for each copy of it) of n cards: the first is `skip` + `step` cards after it,
+// and the rest are every `step` cards after that - so the puzzle's "the next n cards" is skip 0, step 1. As copies
+// are only ever won of later cards, every card's count is settled before it's reached. With a cap, copies that
+// would take a card over it are lost.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CascadeRules {
+  pub skip: usize,
+  pub step: usize,
+  pub cap: Option<BigUint>,
+}
+
+// the puzzle's own rules
+pub const CASCADE_RULES: &str = "skip=0,step=1";
+
+impl FromStr for CascadeRules {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut rules = CascadeRules { skip: 0, step: 1, cap: None };
+    for setting in s.split(',').filter(|setting| !setting.is_empty()) {
+      let invalid = || format!("invalid cascade setting: {} (expected skip=N, step=N or cap=N)", setting);
+      match setting.split_once('=').ok_or_else(invalid)? {
+        ("skip", value) => rules.skip = value.parse().map_err(|_| invalid())?,
+        ("step", value) => rules.step = value.parse().ok().filter(|&step| step > 0).ok_or_else(invalid)?,
+        ("cap", value) => rules.cap = Some(value.parse().ok().filter(|cap: &BigUint| !cap.is_zero()).ok_or_else(invalid)?),
+        _ => return Err(invalid()),
+      }
+    }
+    Ok(rules)
+  }
+}
+
+// How many copies there end up being of each card (original included), and where they came from: for each card,
+// the earlier cards that won copies of it and how many each did. Counts can easily get too big for any fixed size
+// of integer with the wrong rules, so they're arbitrarily big ones.
+pub struct Cascade {
+  pub copies: Vec<BigUint>,
+  pub provenance: Vec<Vec<(usize, BigUint)>>,
+}
+
+pub fn cascade(cards: &[Card], rules: &CascadeRules) -> Cascade {
+  let mut copies = vec![BigUint::one(); cards.len()];
+  let mut provenance = vec![vec![]; cards.len()];
+  for (index, card) in cards.iter().enumerate() {
+    let copies_of_current_card = copies[index].clone();
+    for i in 0..card.matches() {
+      // a huge skip or step just means the card is past the end, rather than wrapping round to an earlier one
+      let new_index = rules.step.checked_mul(i + 1).and_then(|offset| offset.checked_add(rules.skip)).and_then(|offset| offset.checked_add(index));
+      let Some(new_index) = new_index.filter(|&new_index| new_index < cards.len()) else { break };
+      let room = rules.cap.as_ref().map(|cap| if *cap > copies[new_index] { cap - &copies[new_index] } else { BigUint::zero() });
+      let won = match room {
+        Some(room) if room < copies_of_current_card => room,
+        _ => copies_of_current_card.clone(),
+      };
+      if !won.is_zero() {
+        copies[new_index] += &won;
+        provenance[new_index].push((index, won));
+      }
+    }
+  }
+  Cascade { copies, provenance }
+}
+
+fn solve_part_2(cards: Vec<Card>, rules: &CascadeRules) -> BigUint {
+  let cascade = cascade(&cards, rules);
+  for (index, count) in cascade.copies.iter().enumerate() {
+    trace::emit(Level::Detail, "card", &[("card", &(index + 1)), ("copies", count)], || {
+      let sources: Vec<String> = cascade.provenance[index].iter().map(|(source, won)| format!("{} from card {}", won, source + 1)).collect();
+      let sources = if sources.is_empty() { String::new() } else { format!(" - the original, and {}", sources.join(", ")) };
+      let noun = if count.is_one() { "copy" } else { "copies" };
+      format!("card {}: {} {}{}", index + 1, count, noun, sources)
+    });
+  }
+  cascade.copies.iter().sum()
+}
+
+pub fn part_2() -> BigUint {
+  let cards = read_file();
+  solve_part_2(cards, &CASCADE_RULES.parse().unwrap())
+}
+
+pub fn part_2_from_input(input: &str, rules: &CascadeRules) -> BigUint {
+  let cards = parse_input(input);
+  solve_part_2(cards, rules)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // the example from the puzzle, where the cards have 4, 2, 2, 1, 0 and 0 matches
+  const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+  fn example_cascade(rules: &str) -> Cascade {
+    cascade(&parse_input(EXAMPLE), &rules.parse().unwrap())
+  }
+
+  fn numbers(values: &[u64]) -> Vec<BigUint> {
+    values.iter().map(|&value| BigUint::from(value)).collect()
+  }
+
+  fn sources(sources: &[(usize, u64)]) -> Vec<(usize, BigUint)> {
+    sources.iter().map(|&(source, won)| (source, BigUint::from(won))).collect()
+  }
+
+  #[test]
+  fn cascade_rules_are_parsed() {
+    assert_eq!(CASCADE_RULES.parse(), Ok(CascadeRules { skip: 0, step: 1, cap: None }));
+    assert_eq!("cap=1000,skip=2".parse(), Ok(CascadeRules { skip: 2, step: 1, cap: Some(BigUint::from(1000u32)) }));
+    for bad in ["step=0", "cap=0", "skip=-1", "skip", "jump=1", "cap=lots"] {
+      assert!(bad.parse::<CascadeRules>().is_err(), "{}", bad);
+    }
+  }
+
+  #[test]
+  fn the_puzzle_cascade_says_where_each_copy_came_from() {
+    let cascade = example_cascade(CASCADE_RULES);
+    assert_eq!(cascade.copies, numbers(&[1, 2, 4, 8, 14, 1]));
+    assert_eq!(cascade.provenance, [
+      sources(&[]),
+      sources(&[(0, 1)]),
+      sources(&[(0, 1), (1, 2)]),
+      sources(&[(0, 1), (1, 2), (2, 4)]),
+      sources(&[(0, 1), (2, 4), (3, 8)]),
+      sources(&[]),
+    ]);
+    assert_eq!(part_2_from_input(EXAMPLE, &CASCADE_RULES.parse().unwrap()), BigUint::from(30u32));
+  }
+
+  #[test]
+  fn skip_and_step_move_the_window() {
+    // card 1 wins copies of cards 4 and 6 (and 8, which isn't there), card 2 of card 5, and card 3 of card 6
+    let cascade = example_cascade("skip=1,step=2");
+    assert_eq!(cascade.copies, numbers(&[1, 1, 1, 2, 2, 3]));
+    assert_eq!(cascade.provenance[5], sources(&[(0, 1), (2, 1)]));
+
+    // steps and skips too big to add up are past the end too, rather than wrapping round
+    for rules in [format!("skip={}", usize::MAX), format!("step={}", usize::MAX), format!("skip={0},step={0}", usize::MAX)] {
+      assert_eq!(example_cascade(&rules).copies, numbers(&[1; 6]), "{}", rules);
+    }
+  }
+
+  #[test]
+  fn a_cap_loses_the_copies_over_it() {
+    let cascade = example_cascade("cap=3");
+    assert_eq!(cascade.copies, numbers(&[1, 2, 3, 3, 3, 1]));
+    // card 2 could only add one of its two copies to cards 3 and 4, and card 3 none of its three to card 4
+    assert_eq!(cascade.provenance[2], sources(&[(0, 1), (1, 1)]));
+    assert_eq!(cascade.provenance[3], sources(&[(0, 1), (1, 1)]));
+    assert_eq!(cascade.provenance[4], sources(&[(0, 1), (2, 1)]));
+  }
+
+  #[test]
+  fn counts_can_be_bigger_than_any_integer() {
+    // every card wins a copy of all the ones after it, so each has twice as many copies as the one before
+    let cards: Vec<Card> = (0..70u32).rev().map(|later| Card { winning: (0..later).collect(), actual: (0..later).collect() }).collect();
+    let total: BigUint = cascade(&cards, &CASCADE_RULES.parse().unwrap()).copies.iter().sum();
+    assert_eq!(total, (BigUint::one() << 70) - 1u32);
+    assert!(total > BigUint::from(u64::MAX));
+  }
+}
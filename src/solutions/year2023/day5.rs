@@ -0,0 +1,184 @@
+use crate::trace::{self, Level};
+use crate::solutions::checked;
+use crate::solutions::reader;
+
+#[derive(Clone)]
+struct MapLine {
+  destination_start: u64,
+  source_start: u64,
+  range_length: u64,
+}
+
+#[derive(Clone)]
+struct Almanac {
+  seeds: Vec<u64>,
+  maps: Vec<Vec<MapLine>>,
+  // eg. "seed-to-soil" - only used for explaining what's going on
+  map_names: Vec<String>,
+}
+
+fn parse_input(contents: &str) -> Almanac {
+  let contents: &str = &reader::normalize(contents);
+  let sections = reader::sections(contents);
+  let first_line_space_separated: Vec<String> = sections[0].split(" ").map(|s| s.to_owned()).collect();
+  let seeds = first_line_space_separated.split_at(1).1.to_owned().iter().map(|s| s.parse().unwrap()).collect();
+
+  // each map is a section of its own, starting with its "title" line
+  let mut maps = vec![];
+  let mut map_names = vec![];
+  for section in sections.split_at(1).1 {
+    let mut lines = section.lines();
+    map_names.push(lines.next().unwrap().trim_end_matches(" map:").to_owned());
+    let mut current_map = vec![];
+    for line in lines {
+      let nums: Vec<u64> = line.split(" ").map(|s| s.parse().unwrap()).collect();
+      current_map.push(MapLine { destination_start: nums[0], source_start: nums[1], range_length: nums[2] });
+    }
+    maps.push(current_map);
+  }
+
+  Almanac { seeds, maps, map_names }
+}
+
+fn read_file() -> Almanac {
+  parse_input(&reader::read(2023, 5))
+}
+
+fn follow_map_line(map_line: MapLine, num: u64) -> u64 {
+  let MapLine { source_start, destination_start, range_length } = map_line;
+  if num >= source_start && num < checked!(add, source_start, range_length) {
+    checked!(sub, checked!(add, destination_start, num), source_start)
+  } else {
+    num
+  }
+}
+
+fn get_seed_destination(seed: u64, maps: Vec<Vec<MapLine>>, map_names: &[String]) -> u64 {
+  let mut current = seed;
+  for (index, map) in maps.into_iter().enumerate() {
+    let name = map_names.get(index).cloned().unwrap_or(format!("map {}", index + 1));
+    let before = current;
+    for line in map {
+      let (source_start, range_length) = (line.source_start, line.range_length);
+      let result = follow_map_line(line, current);
+      if result != current {
+        trace::emit(Level::Detail, "map hit", &[("seed", &seed), ("map", &name), ("from", &current), ("to", &result)], || {
+          format!("{}: {} is in {}..{}, so maps to {}", name, current, source_start, source_start + range_length - 1, result)
+        });
+        current = result;
+        break;
+      }
+    }
+    if current == before {
+      trace::emit(Level::Everything, "map miss", &[("seed", &seed), ("map", &name), ("value", &current)], || {
+        format!("{}: {} isn't in any of the ranges, so stays the same", name, current)
+      });
+    }
+  }
+  trace::emit(Level::Detail, "seed location", &[("seed", &seed), ("location", &current)], || {
+    format!("seed {} ends up at location {}", seed, current)
+  });
+  current
+}
+
+fn solve_part_1(almanac: Almanac) -> u64 {
+  let Almanac { seeds, maps, map_names } = almanac;
+  let lowest = seeds.iter().map(|&seed| get_seed_destination(seed, maps.clone(), &map_names)).min().unwrap();
+  trace::emit(Level::Summary, "lowest location", &[("location", &lowest)], || format!("the lowest location of any seed is {}", lowest));
+  lowest
+}
+
+pub fn part_1() -> u64 {
+  let almanac = read_file();
+  solve_part_1(almanac)
+}
+
+pub fn part_1_from_input(input: &str) -> u64 {
+  let almanac = parse_input(input);
+  solve_part_1(almanac)
+}
+
+// idea for part 2 - clearly it isn't practically to run through the full algorithm on all billions of inputs listed.
+// But the mappings appear to be laid out in a one-to-one way. Meaning that we can start with 0 as a desired output, easily
+// work through which input seed would be needed to get that, and check if it's in our starting data. If not (of course it
+// won't be!), try 1 and keep going.
+// Although this will probably take tens/hundreds of millions of tries, that's a lot fewer than the "obvious" brute force
+// approach.
+// [turns out to be around 25 seconds when compiled in release mode. Not great for day 5 - but good enough!]
+
+// The seed the maps take to this value, if there's one in the ranges, working back through the maps from the last.
+// A value can come from two places in each map: the line whose destination it's in (no two lines' destinations
+// overlap), and the value itself - but only if it isn't in any line's source, as otherwise it would have been
+// moved somewhere else.
+fn find_seed_for_location(value: u64, maps: &[Vec<MapLine>], is_seed: &impl Fn(u64) -> bool) -> Option<u64> {
+  let Some((map, earlier_maps)) = maps.split_last() else {
+    return Some(value).filter(|&seed| is_seed(seed));
+  };
+  for line in map {
+    if value >= line.destination_start && value < checked!(add, line.destination_start, line.range_length) {
+      let source = checked!(sub, checked!(add, line.source_start, value), line.destination_start);
+      if let Some(seed) = find_seed_for_location(source, earlier_maps, is_seed) {
+        return Some(seed);
+      }
+    }
+  }
+  let in_a_source = map.iter().any(|line| value >= line.source_start && value < checked!(add, line.source_start, line.range_length));
+  if in_a_source { None } else { find_seed_for_location(value, earlier_maps, is_seed) }
+}
+
+fn solve_part_2(almanac: Almanac) -> u64 {
+  let Almanac { seeds, maps, .. } = almanac;
+  // first split the "seeds" into start and end ranges
+  let mut ranges = vec![];
+  for (index, &number) in seeds.iter().enumerate() {
+    if index % 2 == 1 {
+      ranges.push((seeds[index - 1], number));
+    }
+  } 
+  trace::emit(Level::Summary, "seed ranges", &[("count", &ranges.len())], || {
+    format!("there are {} ranges of seeds - working backwards from location 0 until one leads back into them", ranges.len())
+  });
+  let in_range = |seed: u64, &(start, range): &(u64, u64)| start <= seed && seed < checked!(add, start, range);
+  let mut minimum = 0;
+  loop {
+    if let Some(starting_seed) = find_seed_for_location(minimum, &maps, &|seed| ranges.iter().any(|range| in_range(seed, range))) {
+      let &(start, range) = ranges.iter().find(|range| in_range(starting_seed, range)).unwrap();
+      trace::emit(Level::Summary, "seed found", &[("location", &minimum), ("seed", &starting_seed), ("range_start", &start)], || {
+        format!("location {} comes from seed {}, which is in the range {}..{}", minimum, starting_seed, start, start + range - 1)
+      });
+      return minimum;
+    }
+    if minimum % 1000000 == 0 {
+      trace::emit(Level::Detail, "search progress", &[("location", &minimum)], || format!("no luck with any location up to {}", minimum));
+    }
+    minimum += 1;
+  }
+}
+
+pub fn part_2() -> u64 {
+  let almanac = read_file();
+  solve_part_2(almanac)
+}
+
+pub fn part_2_from_input(input: &str) -> u64 {
+  let almanac = parse_input(input);
+  solve_part_2(almanac)
+}
+
+// the "obvious" brute force approach to part 2 which the comment above rules out for the real data: push every
+// single seed in every range forwards through the maps. Only usable on small inputs, but it's so simple that it
+// makes a good reference to check the backwards search against.
+fn solve_part_2_forwards(almanac: Almanac) -> u64 {
+  let Almanac { seeds, maps, map_names } = almanac;
+  seeds
+    .chunks(2)
+    .flat_map(|pair| pair[0]..checked!(add, pair[0], pair[1]))
+    .map(|seed| get_seed_destination(seed, maps.clone(), &map_names))
+    .min()
+    .unwrap()
+}
+
+pub fn part_2_forwards_from_input(input: &str) -> u64 {
+  let almanac = parse_input(input);
+  solve_part_2_forwards(almanac)
+}
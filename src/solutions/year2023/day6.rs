@@ -1,16 +1,12 @@
-use std::fs::File;
-use std::io::prelude::*;
+use crate::solutions::reader;
 
 struct Race {
   time: u64,
   distance: u64,
 }
 
-fn read_file() -> Vec<Race> {
-  let mut file = File::open("./input/input6.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
-
+fn parse_input(contents: &str) -> Vec<Race> {
+  let contents: &str = &reader::normalize(contents);
   let mut races = vec![];
   let mut times = vec![];
   let mut distances = vec![];
@@ -34,6 +30,10 @@ fn read_file() -> Vec<Race> {
   races
 }
 
+fn read_file() -> Vec<Race> {
+  parse_input(&reader::read(2023, 6))
+}
+
 fn ways_to_win(race: &Race) -> u64 {
   for n in 1..race.time {
     let distance = n * (race.time - n);
@@ -55,6 +55,11 @@ pub fn part_1() -> u64 {
   solve_part_1(races)
 }
 
+pub fn part_1_from_input(input: &str) -> u64 {
+  let races = parse_input(input);
+  solve_part_1(races)
+}
+
 fn solve_part_2(races: Vec<Race>) -> u64 {
   let real_time = races.iter().map(|r| r.time.to_string()).collect::<String>().parse().unwrap();
   let real_distance = races.iter().map(|r| r.distance.to_string()).collect::<String>().parse().unwrap();
@@ -65,3 +70,8 @@ pub fn part_2() -> u64 {
   let races = read_file();
   solve_part_2(races)
 }
+
+pub fn part_2_from_input(input: &str) -> u64 {
+  let races = parse_input(input);
+  solve_part_2(races)
+}
@@ -1,7 +1,6 @@
 use std::cmp::Ordering;
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::prelude::*;
+use crate::solutions::reader;
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 enum Card {
@@ -63,14 +62,15 @@ fn read_line(l: &str) -> Hand {
   Hand { cards, bid }
 }
 
-fn read_file() -> Vec<Hand> {
-  let mut file = File::open("./input/input7.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
-
+fn parse_input(contents: &str) -> Vec<Hand> {
+  let contents: &str = &reader::normalize(contents);
   contents.lines().map(read_line).collect()
 }
 
+fn read_file() -> Vec<Hand> {
+  parse_input(&reader::read(2023, 7))
+}
+
 fn get_type(hand: &Hand) -> HandType {
   let mut set_of_cards = HashSet::new();
   for card in hand.cards {
@@ -126,6 +126,11 @@ pub fn part_1() -> u32 {
   solve_part_1(&mut hands)
 }
 
+pub fn part_1_from_input(input: &str) -> u32 {
+  let mut hands = parse_input(input);
+  solve_part_1(&mut hands)
+}
+
 // we need this utility in a couple of different places - to remove jacks/jokers and consider a hand
 // where they are replaced with low-ranking, distinct, "blank" cards
 fn replace_jokers_with_blanks(hand: &Hand) -> Hand {
@@ -205,3 +210,8 @@ pub fn part_2() -> u32 {
   let mut hands = read_file();
   solve_part_2(&mut hands)
 }
+
+pub fn part_2_from_input(input: &str) -> u32 {
+  let mut hands = parse_input(input);
+  solve_part_2(&mut hands)
+}
@@ -1,6 +1,6 @@
-use std::fs::File;
-use std::io::prelude::*;
 use num::integer::lcm;
+use crate::solutions::assumption_failed;
+use crate::solutions::reader;
 
 struct Node {
   name: String,
@@ -35,11 +35,8 @@ fn read_node(s: &str) -> Node {
   Node { name, left, right }
 }
 
-fn read_file() -> Input {
-  let mut file = File::open("./input/input8.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
-
+fn parse_input(contents: &str) -> Input {
+  let contents: &str = &reader::normalize(contents);
   let input_lines: Vec<&str> = contents.lines().collect();
   let path = input_lines[0].chars().map(|c| read_direction(&c)).collect();
   let network = input_lines.split_at(2).1.iter().map(|s| read_node(s)).collect();
@@ -47,6 +44,10 @@ fn read_file() -> Input {
   Input { path, network }
 }
 
+fn read_file() -> Input {
+  parse_input(&reader::read(2023, 8))
+}
+
 fn follow_path(network: &Vec<Node>, current: String, direction: &Direction) -> String {
   let current_node = network.into_iter().find(|n| n.name == current).unwrap();
   match direction {
@@ -75,6 +76,11 @@ pub fn part_1() -> u64 {
   solve_part_1(&network)
 }
 
+pub fn part_1_from_input(input: &str) -> u64 {
+  let network = parse_input(input);
+  solve_part_1(&network)
+}
+
 // This was run to reveal important information about when each of the parallel paths hits an end point:
 #[allow(dead_code)]
 pub fn get_info() {
@@ -117,7 +123,7 @@ fn solve_part_2(input: &Input) -> u64 {
   for location in start_locations {
     let mut steps_taken = 0;
     let mut current_index = 0;
-    let mut current_location = location;
+    let mut current_location = location.clone();
   
     while !current_location.ends_with("Z") {
       let current_direction = &input.path[current_index];
@@ -126,6 +132,21 @@ fn solve_part_2(input: &Input) -> u64 {
       steps_taken += 1;
     }
 
+    // check the "nice properties" above really do hold for this input - otherwise the LCM is just wrong.
+    // Going the same number of steps again should get back to the same endpoint, without passing any other.
+    let endpoint = current_location.clone();
+    for step in 1..=steps_taken {
+      let current_direction = &input.path[current_index];
+      current_location = follow_path(&input.network, current_location, current_direction);
+      current_index = (current_index + 1) % input.path.len();
+      if current_location.ends_with("Z") && (step < steps_taken || current_location != endpoint) {
+        assumption_failed(&format!("the path from {} doesn't hit {} every {} steps", location, endpoint, steps_taken));
+      }
+    }
+    if current_location != endpoint {
+      assumption_failed(&format!("the path from {} doesn't get back to {} after another {} steps", location, endpoint, steps_taken));
+    }
+
     all_base_numbers.push(steps_taken);
   }
 
@@ -141,3 +162,8 @@ pub fn part_2() -> u64 {
   let network = read_file();
   solve_part_2(&network)
 }
+
+pub fn part_2_from_input(input: &str) -> u64 {
+  let network = parse_input(input);
+  solve_part_2(&network)
+}
@@ -1,12 +1,9 @@
-use std::fs::File;
-use std::io::prelude::*;
+use crate::solutions::checked;
+use crate::solutions::reader;
 
 
-fn read_file() -> Vec<Vec<i32>> {
-  let mut file = File::open("./input/input9.txt").unwrap();
-  let mut contents = String::new();
-  file.read_to_string(&mut contents).unwrap();
-
+fn parse_input(contents: &str) -> Vec<Vec<i32>> {
+  let contents: &str = &reader::normalize(contents);
   let mut nums = vec![];
 
   for line in contents.lines() {
@@ -20,6 +17,10 @@ fn read_file() -> Vec<Vec<i32>> {
   nums
 }
 
+fn read_file() -> Vec<Vec<i32>> {
+  parse_input(&reader::read(2023, 9))
+}
+
 fn get_differences(nums: &Vec<i32>) -> Vec<i32> {
   let (first, rest) = nums.split_at(1);
   if rest.len() == 0 {
@@ -28,7 +29,7 @@ fn get_differences(nums: &Vec<i32>) -> Vec<i32> {
   let mut previous = first[0];
   let mut diffs = vec![];
   for &num in rest {
-    diffs.push(num - previous);
+    diffs.push(checked!(sub, num, previous));
     previous = num;
   }
   diffs
@@ -39,13 +40,13 @@ fn get_next_number(nums: Vec<i32>) -> i32 {
   let mut answer = sequence[sequence.len() - 1];
   while !sequence.iter().all(|&n| n == 0) {
     sequence = get_differences(&sequence);
-    answer += sequence[sequence.len() - 1];
+    answer = checked!(add, answer, sequence[sequence.len() - 1]);
   }
   answer
 }
 
 fn solve_part_1(nums: Vec<Vec<i32>>) -> i32 {
-  nums.into_iter().map(get_next_number).sum()
+  nums.into_iter().map(get_next_number).fold(0i32, |total, next| checked!(add, total, next))
 }
 
 pub fn part_1() -> i32 {
@@ -53,6 +54,11 @@ pub fn part_1() -> i32 {
   solve_part_1(nums)
 }
 
+pub fn part_1_from_input(input: &str) -> i32 {
+  let nums = parse_input(input);
+  solve_part_1(nums)
+}
+
 fn get_previous_number(nums: Vec<i32>) -> i32 {
   let mut sequence = nums;
   let mut all_previous = vec![];
@@ -63,16 +69,21 @@ fn get_previous_number(nums: Vec<i32>) -> i32 {
   let mut answer = 0;
   all_previous.reverse();
   for num in all_previous {
-    answer = num - answer;
+    answer = checked!(sub, num, answer);
   }
   answer
 }
 
 fn solve_part_2(nums: Vec<Vec<i32>>) -> i32 {
-  nums.into_iter().map(get_previous_number).sum()
+  nums.into_iter().map(get_previous_number).fold(0i32, |total, previous| checked!(add, total, previous))
 }
 
 pub fn part_2() -> i32 {
   let nums = read_file();
   solve_part_2(nums)
 }
+
+pub fn part_2_from_input(input: &str) -> i32 {
+  let nums = parse_input(input);
+  solve_part_2(nums)
+}
@@ -0,0 +1,498 @@
+#[cfg(feature = "year2023-day1")]
+pub mod day1;
+#[cfg(feature = "year2023-day2")]
+pub mod day2;
+#[cfg(feature = "year2023-day3")]
+pub mod day3;
+#[cfg(feature = "year2023-day4")]
+pub mod day4;
+#[cfg(feature = "year2023-day5")]
+pub mod day5;
+#[cfg(feature = "year2023-day6")]
+pub mod day6;
+#[cfg(feature = "year2023-day7")]
+pub mod day7;
+#[cfg(feature = "year2023-day8")]
+pub mod day8;
+#[cfg(feature = "year2023-day9")]
+pub mod day9;
+#[cfg(feature = "year2023-day10")]
+pub mod day10;
+#[cfg(feature = "year2023-day11")]
+pub mod day11;
+#[cfg(feature = "year2023-day12")]
+pub mod day12;
+#[cfg(feature = "year2023-day13")]
+pub mod day13;
+#[cfg(feature = "year2023-day14")]
+pub mod day14;
+#[cfg(feature = "year2023-day15")]
+pub mod day15;
+#[cfg(feature = "year2023-day16")]
+pub mod day16;
+#[cfg(feature = "year2023-day17")]
+pub mod day17;
+#[cfg(feature = "year2023-day18")]
+pub mod day18;
+#[cfg(feature = "year2023-day19")]
+pub mod day19;
+#[cfg(feature = "year2023-day20")]
+pub mod day20;
+#[cfg(feature = "year2023-day21")]
+pub mod day21;
+#[cfg(feature = "year2023-day22")]
+pub mod day22;
+#[cfg(feature = "year2023-day23")]
+pub mod day23;
+#[cfg(feature = "year2023-day24")]
+pub mod day24;
+#[cfg(feature = "year2023-day25")]
+pub mod day25;
+
+use super::{parses, Day, InputShape, Param};
+
+// the original way of getting the answers - run everything, one after the other
+pub fn run() {
+  #[cfg(feature = "year2023-day1")]
+  {
+    println!("The answer to day 1, part 1 is {}", day1::part_1());
+    println!("The answer to day 1, part 2 is {}", day1::part_2());
+  }
+  #[cfg(feature = "year2023-day2")]
+  {
+    println!("The answer to day 2, part 1 is {}", day2::part_1());
+    println!("The answer to day 2, part 2 is {}", day2::part_2());
+  }
+  #[cfg(feature = "year2023-day3")]
+  {
+    println!("The answer to day 3, part 1 is {}", day3::part_1());
+    println!("The answer to day 3, part 2 is {}", day3::part_2());
+  }
+  #[cfg(feature = "year2023-day4")]
+  {
+    println!("The answer to day 4, part 1 is {}", day4::part_1());
+    println!("The answer to day 4, part 2 is {}", day4::part_2());
+  }
+  #[cfg(feature = "year2023-day5")]
+  {
+    println!("The answer to day 5, part 1 is {}", day5::part_1());
+    println!("The answer to day 5, part 2 is {}", day5::part_2());
+  }
+  #[cfg(feature = "year2023-day6")]
+  {
+    println!("The answer to day 6, part 1 is {}", day6::part_1());
+    println!("The answer to day 6, part 2 is {}", day6::part_2());
+  }
+  #[cfg(feature = "year2023-day7")]
+  {
+    println!("The answer to day 7, part 1 is {}", day7::part_1());
+    println!("The answer to day 7, part 2 is {}", day7::part_2());
+  }
+  #[cfg(feature = "year2023-day8")]
+  {
+    println!("The answer to day 8, part 1 is {}", day8::part_1());
+    // day8::get_info();
+    println!("The answer to day 8, part 2 is {}", day8::part_2());
+  }
+  #[cfg(feature = "year2023-day9")]
+  {
+    println!("The answer to day 9, part 1 is {}", day9::part_1());
+    println!("The answer to day 9, part 2 is {}", day9::part_2());
+  }
+  #[cfg(feature = "year2023-day10")]
+  {
+    println!("The answer to day 10, part 1 is {}", day10::part_1());
+    println!("The answer to day 10, part 2 is {}", day10::part_2());
+  }
+  #[cfg(feature = "year2023-day11")]
+  {
+    println!("The answer to day 11, part 1 is {}", day11::part_1());
+    println!("The answer to day 11, part 2 is {}", day11::part_2());
+  }
+  #[cfg(feature = "year2023-day12")]
+  {
+    println!("The answer to day 12, part 1 is {}", day12::part_1());
+    println!("The answer to day 12, part 2 is {}", day12::part_2());
+  }
+  #[cfg(feature = "year2023-day13")]
+  {
+    println!("The answer to day 13, part 1 is {}", day13::part_1());
+    println!("The answer to day 13, part 2 is {}", day13::part_2());
+  }
+  #[cfg(feature = "year2023-day14")]
+  {
+    println!("The answer to day 14, part 1 is {}", day14::part_1());
+    println!("The answer to day 14, part 2 is {}", day14::part_2());
+  }
+  #[cfg(feature = "year2023-day15")]
+  {
+    println!("The answer to day 15, part 1 is {}", day15::part_1());
+    println!("The answer to day 15, part 2 is {}", day15::part_2());
+  }
+  #[cfg(feature = "year2023-day16")]
+  {
+    println!("The answer to day 16, part 1 is {}", day16::part_1());
+    println!("The answer to day 16, part 2 is {}", day16::part_2());
+  }
+  #[cfg(feature = "year2023-day17")]
+  {
+    println!("The answer to day 17, part 1 is {}", day17::describe(day17::part_1()));
+    println!("The answer to day 17, part 2 is {}", day17::describe(day17::part_2()));
+  }
+  #[cfg(feature = "year2023-day18")]
+  {
+    println!("The answer to day 18, part 1 is {}", day18::part_1());
+    println!("The answer to day 18, part 2 is {}", day18::part_2());
+  }
+  #[cfg(feature = "year2023-day19")]
+  {
+    println!("The answer to day 19, part 1 is {}", day19::part_1());
+    println!("The answer to day 19, part 2 is {}", day19::part_2());
+  }
+  #[cfg(feature = "year2023-day20")]
+  {
+    println!("The answer to day 20, part 1 is {}", day20::part_1());
+    println!("The answer to day 20, part 2 is {}", day20::part_2());
+  }
+  #[cfg(feature = "year2023-day21")]
+  {
+    println!("The answer to day 21, part 1 is {}", day21::part_1());
+    println!("The answer to day 21, part 2 is {}", day21::part_2());
+  }
+  #[cfg(feature = "year2023-day22")]
+  {
+    println!("The answer to day 22, part 1 is {}", day22::part_1());
+    println!("The answer to day 22, part 2 is {}", day22::part_2());
+  }
+  #[cfg(feature = "year2023-day23")]
+  {
+    println!("The answer to day 23, part 1 is {}", day23::part_1());
+    println!("The answer to day 23, part 2 is {}", day23::part_2());
+  }
+  #[cfg(feature = "year2023-day24")]
+  {
+    println!("The answer to day 24, part 1 is {}", day24::part_1());
+    println!("The answer to day 24, part 2 is {}", day24::part_2());
+  }
+  #[cfg(feature = "year2023-day25")]
+  {
+    println!("The answer to day 25, part 1 is {}", day25::part_1());
+  }
+}
+
+// the table of solutions for the registry (see the parent module) - just the days that were compiled in
+pub fn days() -> Vec<Day> {
+  vec![
+    #[cfg(feature = "year2023-day1")]
+    Day {
+      year: 2023,
+      number: 1,
+      shape: InputShape::Lines,
+      params: &[
+        Param { name: "number-words", check: day1::check_number_words },
+        Param { name: "calibration-rule", check: parses::<day1::Rule> },
+      ],
+      check_params: None,
+      part_1: |input, params| day1::part_1_from_input(input, params.get("calibration-rule", day1::Rule::FirstAndLast)).to_string(),
+      part_2: Some(|input, params| {
+        // the dictionary was checked when the parameters were read, so loading it only fails if it's since gone
+        let words = day1::NumberWords::load(&params.get("number-words", String::from(day1::NUMBER_WORDS))).unwrap_or_default();
+        let rule = params.get("calibration-rule", day1::Rule::FirstAndLast);
+        day1::part_2_from_input(input, &words, rule).to_string()
+      }),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day2")]
+    Day {
+      year: 2023,
+      number: 2,
+      shape: InputShape::Lines,
+      params: &[Param { name: "bag", check: parses::<day2::Cubes> }],
+      check_params: None,
+      part_1: |input, params| day2::part_1_from_input(input, &params.get("bag", day2::BAG.parse().unwrap())).to_string(),
+      part_2: Some(|input, _| day2::part_2_from_input(input).to_string()),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day3")]
+    Day {
+      year: 2023,
+      number: 3,
+      shape: InputShape::Grid,
+      params: &[Param { name: "symbol-rules", check: parses::<day3::SymbolRules> }],
+      check_params: None,
+      part_1: |input, params| day3::part_1_from_input(input, &params.get("symbol-rules", day3::SYMBOL_RULES.parse().unwrap())).to_string(),
+      part_2: Some(|input, params| day3::part_2_from_input(input, &params.get("symbol-rules", day3::SYMBOL_RULES.parse().unwrap())).to_string()),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day4")]
+    Day {
+      year: 2023,
+      number: 4,
+      shape: InputShape::Lines,
+      params: &[Param { name: "cascade-rules", check: parses::<day4::CascadeRules> }],
+      check_params: None,
+      part_1: |input, _| day4::part_1_from_input(input).to_string(),
+      part_2: Some(|input, params| day4::part_2_from_input(input, &params.get("cascade-rules", day4::CASCADE_RULES.parse().unwrap())).to_string()),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day5")]
+    Day {
+      year: 2023,
+      number: 5,
+      shape: InputShape::Sections,
+      params: &[],
+      check_params: None,
+      part_1: |input, _| day5::part_1_from_input(input).to_string(),
+      part_2: Some(|input, _| day5::part_2_from_input(input).to_string()),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day6")]
+    Day {
+      year: 2023,
+      number: 6,
+      shape: InputShape::Lines,
+      params: &[],
+      check_params: None,
+      part_1: |input, _| day6::part_1_from_input(input).to_string(),
+      part_2: Some(|input, _| day6::part_2_from_input(input).to_string()),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day7")]
+    Day {
+      year: 2023,
+      number: 7,
+      shape: InputShape::Lines,
+      params: &[],
+      check_params: None,
+      part_1: |input, _| day7::part_1_from_input(input).to_string(),
+      part_2: Some(|input, _| day7::part_2_from_input(input).to_string()),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day8")]
+    Day {
+      year: 2023,
+      number: 8,
+      shape: InputShape::Sections,
+      params: &[],
+      check_params: None,
+      part_1: |input, _| day8::part_1_from_input(input).to_string(),
+      part_2: Some(|input, _| day8::part_2_from_input(input).to_string()),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day9")]
+    Day {
+      year: 2023,
+      number: 9,
+      shape: InputShape::Lines,
+      params: &[],
+      check_params: None,
+      part_1: |input, _| day9::part_1_from_input(input).to_string(),
+      part_2: Some(|input, _| day9::part_2_from_input(input).to_string()),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day10")]
+    Day {
+      year: 2023,
+      number: 10,
+      shape: InputShape::Grid,
+      params: &[],
+      check_params: None,
+      part_1: |input, _| day10::part_1_from_input(input).to_string(),
+      part_2: Some(|input, _| day10::part_2_from_input(input).to_string()),
+      render: Some(day10::render_from_input),
+    },
+    #[cfg(feature = "year2023-day11")]
+    Day {
+      year: 2023,
+      number: 11,
+      shape: InputShape::Grid,
+      params: &[Param { name: "expansion-factor", check: parses::<u64> }],
+      check_params: None,
+      part_1: |input, _| day11::part_1_from_input(input).to_string(),
+      part_2: Some(|input, params| day11::part_2_from_input(input, params.get("expansion-factor", day11::EXPANSION_FACTOR)).to_string()),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day12")]
+    Day {
+      year: 2023,
+      number: 12,
+      shape: InputShape::Lines,
+      params: &[Param { name: "unfold-copies", check: parses::<usize> }],
+      check_params: None,
+      part_1: |input, _| day12::part_1_from_input(input).to_string(),
+      part_2: Some(|input, params| day12::part_2_from_input(input, params.get("unfold-copies", day12::UNFOLD_COPIES)).to_string()),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day13")]
+    Day {
+      year: 2023,
+      number: 13,
+      shape: InputShape::Sections,
+      params: &[],
+      check_params: None,
+      part_1: |input, _| day13::part_1_from_input(input).to_string(),
+      part_2: Some(|input, _| day13::part_2_from_input(input).to_string()),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day14")]
+    Day {
+      year: 2023,
+      number: 14,
+      shape: InputShape::Grid,
+      params: &[Param { name: "cycles", check: parses::<usize> }],
+      check_params: None,
+      part_1: |input, _| day14::part_1_from_input(input).to_string(),
+      part_2: Some(|input, params| day14::part_2_from_input(input, params.get("cycles", day14::CYCLES)).to_string()),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day15")]
+    Day {
+      year: 2023,
+      number: 15,
+      shape: InputShape::CommaSeparated,
+      params: &[],
+      check_params: None,
+      part_1: |input, _| day15::part_1_from_input(input).to_string(),
+      part_2: Some(|input, _| day15::part_2_from_input(input).to_string()),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day16")]
+    Day {
+      year: 2023,
+      number: 16,
+      shape: InputShape::Grid,
+      params: &[],
+      check_params: None,
+      part_1: |input, _| day16::part_1_from_input(input).to_string(),
+      part_2: Some(|input, _| day16::part_2_from_input(input).to_string()),
+      render: Some(day16::render_from_input),
+    },
+    #[cfg(feature = "year2023-day17")]
+    Day {
+      year: 2023,
+      number: 17,
+      shape: InputShape::Grid,
+      params: &[
+        Param { name: "part-1-min-distance", check: parses::<usize> },
+        Param { name: "part-1-max-distance", check: parses::<usize> },
+        Param { name: "part-2-min-distance", check: parses::<usize> },
+        Param { name: "part-2-max-distance", check: parses::<usize> },
+      ],
+      check_params: Some(|params| {
+        let part_1_min = params.get("part-1-min-distance", day17::PART_1_MIN_DISTANCE);
+        day17::check_distances(1, part_1_min, params.get("part-1-max-distance", day17::PART_1_MAX_DISTANCE))?;
+        let part_2_min = params.get("part-2-min-distance", day17::PART_2_MIN_DISTANCE);
+        day17::check_distances(2, part_2_min, params.get("part-2-max-distance", day17::PART_2_MAX_DISTANCE))
+      }),
+      part_1: |input, params| {
+        let min_distance = params.get("part-1-min-distance", day17::PART_1_MIN_DISTANCE);
+        let max_distance = params.get("part-1-max-distance", day17::PART_1_MAX_DISTANCE);
+        day17::describe(day17::part_1_from_input(input, min_distance, max_distance))
+      },
+      part_2: Some(|input, params| {
+        let min_distance = params.get("part-2-min-distance", day17::PART_2_MIN_DISTANCE);
+        let max_distance = params.get("part-2-max-distance", day17::PART_2_MAX_DISTANCE);
+        day17::describe(day17::part_2_from_input(input, min_distance, max_distance))
+      }),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day18")]
+    Day {
+      year: 2023,
+      number: 18,
+      shape: InputShape::Lines,
+      params: &[],
+      check_params: None,
+      part_1: |input, _| day18::part_1_from_input(input).to_string(),
+      part_2: Some(|input, _| day18::part_2_from_input(input).to_string()),
+      render: Some(day18::render_from_input),
+    },
+    #[cfg(feature = "year2023-day19")]
+    Day {
+      year: 2023,
+      number: 19,
+      shape: InputShape::Sections,
+      params: &[],
+      check_params: None,
+      part_1: |input, _| day19::part_1_from_input(input).to_string(),
+      part_2: Some(|input, _| day19::part_2_from_input(input).to_string()),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day20")]
+    Day {
+      year: 2023,
+      number: 20,
+      shape: InputShape::Lines,
+      params: &[Param { name: "presses", check: parses::<usize> }],
+      check_params: None,
+      part_1: |input, params| day20::part_1_from_input(input, params.get("presses", day20::BUTTON_PRESSES)).to_string(),
+      part_2: Some(|input, _| day20::part_2_from_input(input).to_string()),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day21")]
+    Day {
+      year: 2023,
+      number: 21,
+      shape: InputShape::Grid,
+      params: &[
+        Param { name: "part-1-steps", check: parses::<usize> },
+        Param { name: "part-2-steps", check: parses::<u64> },
+      ],
+      check_params: None,
+      part_1: |input, params| day21::part_1_from_input(input, params.get("part-1-steps", day21::PART_1_STEPS)).to_string(),
+      part_2: Some(|input, params| day21::part_2_from_input(input, params.get("part-2-steps", day21::PART_2_STEPS)).to_string()),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day22")]
+    Day {
+      year: 2023,
+      number: 22,
+      shape: InputShape::Lines,
+      params: &[],
+      check_params: None,
+      part_1: |input, _| day22::part_1_from_input(input).to_string(),
+      part_2: Some(|input, _| day22::part_2_from_input(input).to_string()),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day23")]
+    Day {
+      year: 2023,
+      number: 23,
+      shape: InputShape::Grid,
+      params: &[],
+      check_params: None,
+      part_1: |input, _| day23::part_1_from_input(input).to_string(),
+      part_2: Some(|input, _| day23::part_2_from_input(input).to_string()),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day24")]
+    Day {
+      year: 2023,
+      number: 24,
+      shape: InputShape::Lines,
+      params: &[
+        Param { name: "test-area-min", check: parses::<f64> },
+        Param { name: "test-area-max", check: parses::<f64> },
+      ],
+      check_params: None,
+      part_1: |input, params| {
+        let min_value = params.get("test-area-min", day24::TEST_AREA_MIN);
+        let max_value = params.get("test-area-max", day24::TEST_AREA_MAX);
+        day24::part_1_from_input(input, min_value, max_value).to_string()
+      },
+      part_2: Some(|input, _| day24::part_2_from_input(input).to_string()),
+      render: None,
+    },
+    #[cfg(feature = "year2023-day25")]
+    Day {
+      year: 2023,
+      number: 25,
+      shape: InputShape::Lines,
+      params: &[],
+      check_params: None,
+      part_1: |input, _| day25::part_1_from_input(input).to_string(),
+      part_2: None,
+      render: None,
+    },
+  ]
+}
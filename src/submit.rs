@@ -0,0 +1,336 @@
+use crate::inputs::InputCache;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Submits answers to the site, keeping a log of every attempt so that we never send the same wrong answer twice,
+// or one that we already know must be wrong because an earlier guess was "too high" or "too low". The log lives
+// next to the cached inputs (see `inputs`), one file per year and session, with a line per attempt:
+//
+//   <unix time>\t<day>\t<part>\t<verdict>\t<answer>
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Verdict {
+  Correct,
+  TooHigh,
+  TooLow,
+  Wrong,
+  // submitted too soon after the last one - this doesn't tell us anything about the answer
+  RateLimited,
+  // the part is already solved, or not unlocked yet
+  WrongLevel,
+}
+
+impl Verdict {
+  fn name(&self) -> &'static str {
+    match self {
+      Verdict::Correct => "correct",
+      Verdict::TooHigh => "too-high",
+      Verdict::TooLow => "too-low",
+      Verdict::Wrong => "wrong",
+      Verdict::RateLimited => "rate-limited",
+      Verdict::WrongLevel => "wrong-level",
+    }
+  }
+
+  pub fn describe(&self) -> &'static str {
+    match self {
+      Verdict::Correct => "that's the right answer!",
+      Verdict::TooHigh => "wrong - the answer is too high",
+      Verdict::TooLow => "wrong - the answer is too low",
+      Verdict::Wrong => "wrong",
+      Verdict::RateLimited => "submitted too recently - wait a bit and try again",
+      Verdict::WrongLevel => "that part is either already solved or not unlocked yet",
+    }
+  }
+
+  // whether the answer given definitely isn't the right one
+  fn rules_out_answer(&self) -> bool {
+    matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+  }
+
+  // works out the verdict from the page the site sends back, which is meant for people rather than programs
+  fn from_response(page: &str) -> Option<Verdict> {
+    if page.contains("That's the right answer") {
+      Some(Verdict::Correct)
+    } else if page.contains("answer is too high") {
+      Some(Verdict::TooHigh)
+    } else if page.contains("answer is too low") {
+      Some(Verdict::TooLow)
+    } else if page.contains("That's not the right answer") {
+      Some(Verdict::Wrong)
+    } else if page.contains("You gave an answer too recently") {
+      Some(Verdict::RateLimited)
+    } else if page.contains("You don't seem to be solving the right level") {
+      Some(Verdict::WrongLevel)
+    } else {
+      None
+    }
+  }
+}
+
+impl FromStr for Verdict {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let all = [Verdict::Correct, Verdict::TooHigh, Verdict::TooLow, Verdict::Wrong, Verdict::RateLimited, Verdict::WrongLevel];
+    all.into_iter().find(|verdict| verdict.name() == s).ok_or(format!("unknown verdict: {}", s))
+  }
+}
+
+struct Attempt {
+  day: u8,
+  part: u8,
+  verdict: Verdict,
+  answer: String,
+}
+
+fn log_path(cache: &InputCache, year: u16) -> Result<PathBuf, String> {
+  Ok(cache.session_dir(year)?.join("answers.log"))
+}
+
+fn read_log(cache: &InputCache, year: u16) -> Result<Vec<Attempt>, String> {
+  let contents = match fs::read_to_string(log_path(cache, year)?) {
+    Ok(contents) => contents,
+    Err(_) => return Ok(vec![]),
+  };
+  contents
+    .lines()
+    .filter(|line| !line.is_empty())
+    .map(|line| {
+      let fields: Vec<&str> = line.splitn(5, '\t').collect();
+      match fields.as_slice() {
+        [_, day, part, verdict, answer] => Ok(Attempt {
+          day: day.parse().map_err(|_| format!("bad line in answer log: {}", line))?,
+          part: part.parse().map_err(|_| format!("bad line in answer log: {}", line))?,
+          verdict: verdict.parse()?,
+          answer: answer.to_string(),
+        }),
+        _ => Err(format!("bad line in answer log: {}", line)),
+      }
+    })
+    .collect()
+}
+
+fn append_to_log(cache: &InputCache, year: u16, attempt: &Attempt) -> Result<(), String> {
+  let path = log_path(cache, year)?;
+  fs::create_dir_all(path.parent().unwrap()).map_err(|error| error.to_string())?;
+  let mut file = OpenOptions::new().create(true).append(true).open(&path).map_err(|error| error.to_string())?;
+  let time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+  writeln!(file, "{}\t{}\t{}\t{}\t{}", time, attempt.day, attempt.part, attempt.verdict.name(), attempt.answer)
+    .map_err(|error| error.to_string())
+}
+
+// Checks an answer against everything we've learned from earlier attempts, returning why it's not worth
+// sending if that's the case. The bounds only apply to answers that are whole numbers, which is all of them
+// this year.
+fn check_against_log(attempts: &[Attempt], answer: &str) -> Result<(), String> {
+  if let Some(correct) = attempts.iter().find(|attempt| attempt.verdict == Verdict::Correct) {
+    return Err(if correct.answer == answer {
+      format!("{} was already accepted as the right answer", answer)
+    } else {
+      format!("this part is already solved, with {} - so {} must be wrong", correct.answer, answer)
+    });
+  }
+  if let Some(wrong) = attempts.iter().find(|attempt| attempt.verdict.rules_out_answer() && attempt.answer == answer) {
+    return Err(format!("{} has already been tried ({})", answer, wrong.verdict.describe()));
+  }
+
+  if let Ok(value) = answer.parse::<i128>() {
+    let numbers_with = |verdict: Verdict| attempts.iter().filter(move |attempt| attempt.verdict == verdict).filter_map(|attempt| attempt.answer.parse::<i128>().ok());
+    if let Some(lowest_too_high) = numbers_with(Verdict::TooHigh).min() {
+      if value >= lowest_too_high {
+        return Err(format!("{} can't be right - {} was already too high", answer, lowest_too_high));
+      }
+    }
+    if let Some(highest_too_low) = numbers_with(Verdict::TooLow).max() {
+      if value <= highest_too_low {
+        return Err(format!("{} can't be right - {} was already too low", answer, highest_too_low));
+      }
+    }
+  }
+  Ok(())
+}
+
+// the answer as it goes in the form body - they're all numbers really, but just in case
+fn form_encode(value: &str) -> String {
+  value
+    .bytes()
+    .map(|byte| match byte {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (byte as char).to_string(),
+      _ => format!("%{:02X}", byte),
+    })
+    .collect()
+}
+
+// Submits an answer for one part of one day, unless the log shows that it can't be right, and records the
+// result in the log. Refusing to send an answer is an error, as is a response we can't make sense of.
+pub fn submit(cache: &InputCache, year: u16, day: u8, part: u8, answer: &str) -> Result<Verdict, String> {
+  let answer = answer.trim();
+  if answer.is_empty() || answer.contains(char::is_whitespace) {
+    return Err(format!("not a valid answer: {:?}", answer));
+  }
+  let attempts: Vec<Attempt> = read_log(cache, year)?.into_iter().filter(|attempt| attempt.day == day && attempt.part == part).collect();
+  check_against_log(&attempts, answer)?;
+
+  let session = cache.session()?;
+  cache.wait_for_rate_limit()?;
+  let url = format!("{}/{}/day/{}/answer", cache.base_url.trim_end_matches('/'), year, day);
+  let form = format!("level={}&answer={}", part, form_encode(answer));
+  let (status, page) = cache.client.post_form(&url, &format!("session={}", session), &form)?;
+  if status != 200 {
+    return Err(format!("unexpected response {} from {}", status, url));
+  }
+  let verdict = Verdict::from_response(&page).ok_or(format!("couldn't tell what the response from {} meant", url))?;
+  append_to_log(cache, year, &Attempt { day, part, verdict, answer: answer.to_owned() })?;
+  Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::inputs::HttpClient;
+  use std::cell::RefCell;
+  use std::env;
+  use std::rc::Rc;
+  use std::time::Duration;
+
+  // A stand-in for the site that knows the right answer to every part, and replies with the same wording the real
+  // one uses. It keeps a count of the submissions it has received, so that the tests can tell when an answer was
+  // refused without being sent.
+  struct StandInSite {
+    answer: i64,
+    submissions: Rc<RefCell<usize>>,
+    rate_limit_next: Rc<RefCell<bool>>,
+  }
+
+  impl HttpClient for StandInSite {
+    fn get(&self, _url: &str, _cookie: &str) -> Result<(u16, String), String> {
+      Ok((404, String::from("404 Not Found")))
+    }
+
+    fn post_form(&self, _url: &str, _cookie: &str, form: &str) -> Result<(u16, String), String> {
+      *self.submissions.borrow_mut() += 1;
+      if self.rate_limit_next.replace(false) {
+        return Ok((200, String::from("<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 28s left to wait.</p>")));
+      }
+      let answer = form.split('&').find_map(|field| field.strip_prefix("answer=")).unwrap_or("");
+      let page = match answer.parse::<i64>() {
+        Ok(value) if value == self.answer => "<p>That's the right answer!  You are one gold star closer to saving Christmas.</p>",
+        Ok(value) if value > self.answer => "<p>That's not the right answer; your answer is too high.  Please wait one minute.</p>",
+        Ok(_) => "<p>That's not the right answer; your answer is too low.  Please wait one minute.</p>",
+        Err(_) => "<p>That's not the right answer.  If you're stuck, make sure you're using the full input data.</p>",
+      };
+      Ok((200, String::from(page)))
+    }
+  }
+
+  // a cache in an empty directory of its own, submitting to a stand-in site whose answer is 500
+  struct Setup {
+    cache: InputCache,
+    submissions: Rc<RefCell<usize>>,
+    rate_limit_next: Rc<RefCell<bool>>,
+  }
+
+  impl Setup {
+    fn new(test: &str) -> Setup {
+      let dir = env::temp_dir().join(format!("aoc-submit-test-{}-{}", test, std::process::id()));
+      let _ = fs::remove_dir_all(&dir);
+      let submissions = Rc::new(RefCell::new(0));
+      let rate_limit_next = Rc::new(RefCell::new(false));
+      let cache = InputCache {
+        dir,
+        base_url: String::from("http://stand-in"),
+        session: Some(String::from("alice")),
+        min_interval: Duration::ZERO,
+        client: Box::new(StandInSite { answer: 500, submissions: submissions.clone(), rate_limit_next: rate_limit_next.clone() }),
+      };
+      Setup { cache, submissions, rate_limit_next }
+    }
+
+    // submits an answer to day 1, giving the result and whether it actually reached the site
+    fn submit(&self, part: u8, answer: &str) -> (Result<Verdict, String>, bool) {
+      let before = *self.submissions.borrow();
+      let result = submit(&self.cache, 2023, 1, part, answer);
+      (result, *self.submissions.borrow() > before)
+    }
+
+    // submits an answer that should be refused without being sent, giving why
+    fn refused(&self, part: u8, answer: &str) -> String {
+      let (result, sent) = self.submit(part, answer);
+      assert!(!sent, "{} was sent", answer);
+      result.unwrap_err()
+    }
+  }
+
+  impl Drop for Setup {
+    fn drop(&mut self) {
+      let _ = fs::remove_dir_all(&self.cache.dir);
+    }
+  }
+
+  #[test]
+  fn refuses_an_answer_already_known_to_be_wrong() {
+    let setup = Setup::new("known-wrong");
+    assert_eq!(setup.submit(1, "600"), (Ok(Verdict::TooHigh), true));
+    assert!(setup.refused(1, "600").contains("already been tried"));
+    assert_eq!(setup.submit(1, "1e3"), (Ok(Verdict::Wrong), true));
+    assert!(setup.refused(1, "1e3").contains("already been tried"));
+  }
+
+  #[test]
+  fn refuses_answers_outside_the_known_bounds() {
+    let setup = Setup::new("bounds");
+    assert_eq!(setup.submit(1, "600"), (Ok(Verdict::TooHigh), true));
+    assert!(setup.refused(1, "700").contains("was already too high"));
+    assert_eq!(setup.submit(1, "100"), (Ok(Verdict::TooLow), true));
+    assert!(setup.refused(1, "50").contains("was already too low"));
+    // anything between the bounds is still worth a try
+    assert_eq!(setup.submit(1, "400"), (Ok(Verdict::TooLow), true));
+  }
+
+  #[test]
+  fn refuses_malformed_answers() {
+    let setup = Setup::new("malformed");
+    assert!(setup.refused(1, "4 2").contains("not a valid answer"));
+    assert!(setup.refused(1, "  ").contains("not a valid answer"));
+  }
+
+  #[test]
+  fn a_rate_limited_answer_can_be_sent_again() {
+    let setup = Setup::new("rate-limited");
+    *setup.rate_limit_next.borrow_mut() = true;
+    assert_eq!(setup.submit(1, "400"), (Ok(Verdict::RateLimited), true));
+    assert_eq!(setup.submit(1, "400"), (Ok(Verdict::TooLow), true));
+  }
+
+  #[test]
+  fn nothing_more_is_sent_once_a_part_is_solved() {
+    let setup = Setup::new("solved");
+    assert_eq!(setup.submit(1, "500"), (Ok(Verdict::Correct), true));
+    assert!(setup.refused(1, "500").contains("already accepted"));
+    assert!(setup.refused(1, "499").contains("already solved"));
+    // the other part has a log of its own
+    assert_eq!(setup.submit(2, "600"), (Ok(Verdict::TooHigh), true));
+  }
+
+  #[test]
+  fn the_log_reads_back_what_was_written() {
+    let setup = Setup::new("log");
+    let verdicts = [Verdict::Correct, Verdict::TooHigh, Verdict::TooLow, Verdict::Wrong, Verdict::RateLimited, Verdict::WrongLevel];
+    let written: Vec<(u8, u8, Verdict, String)> = verdicts.iter().enumerate().map(|(index, &verdict)| {
+      (index as u8 + 1, index as u8 % 2 + 1, verdict, format!("answer-{}", index))
+    }).collect();
+    for (day, part, verdict, answer) in written.iter() {
+      append_to_log(&setup.cache, 2023, &Attempt { day: *day, part: *part, verdict: *verdict, answer: answer.clone() }).unwrap();
+    }
+    let read: Vec<(u8, u8, Verdict, String)> = read_log(&setup.cache, 2023).unwrap().into_iter().map(|attempt| {
+      (attempt.day, attempt.part, attempt.verdict, attempt.answer)
+    }).collect();
+    assert_eq!(read, written);
+    // and another year's log is separate
+    assert!(read_log(&setup.cache, 2022).unwrap().is_empty());
+  }
+}
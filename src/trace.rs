@@ -0,0 +1,98 @@
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Mutex;
+
+// A way for the solutions to say what they're doing while they run, for the `--explain` option. Solutions emit
+// events - a kind (eg. "map hit"), some named values, and a sentence describing it - each at one of a few levels
+// of detail. Nothing is recorded unless tracing has been turned on at that level or above, so the cost when it's
+// off is just checking the level.
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+pub enum Level {
+  Off = 0,
+  // the big picture - a handful of events per part
+  Summary = 1,
+  // a line or so for each "unit" of the puzzle: each seed, each part, each grid...
+  Detail = 2,
+  // every single step, which can be a lot
+  Everything = 3,
+}
+
+impl FromStr for Level {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "off" => Ok(Level::Off),
+      "summary" => Ok(Level::Summary),
+      "detail" => Ok(Level::Detail),
+      "everything" => Ok(Level::Everything),
+      other => Err(format!("unknown explain level: {} (expected summary, detail or everything)", other)),
+    }
+  }
+}
+
+pub struct Event {
+  pub level: Level,
+  pub kind: &'static str,
+  pub fields: Vec<(&'static str, String)>,
+  pub message: String,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+static EVENTS: Mutex<Vec<Event>> = Mutex::new(vec![]);
+
+pub fn set_level(level: Level) {
+  LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+// worth checking before doing any extra work just to be able to emit an event
+pub fn enabled(level: Level) -> bool {
+  level != Level::Off && level as u8 <= LEVEL.load(Ordering::Relaxed)
+}
+
+// records an event, if tracing is on at this level. The message is only built if it's going to be kept.
+pub fn emit(level: Level, kind: &'static str, fields: &[(&'static str, &dyn Display)], message: impl FnOnce() -> String) {
+  if !enabled(level) {
+    return;
+  }
+  let fields = fields.iter().map(|(name, value)| (*name, value.to_string())).collect();
+  EVENTS.lock().unwrap().push(Event { level, kind, fields, message: message() });
+}
+
+// everything recorded so far, which is then forgotten about
+pub fn take_events() -> Vec<Event> {
+  std::mem::take(&mut *EVENTS.lock().unwrap())
+}
+
+// Something the person running a solution ought to hear about whether or not they asked for an explanation, like
+// day 1 leaving lines it couldn't make sense of out of the total. These are always kept, and it's up to the command
+// that ran the solution whether to show them, so that nothing is printed from inside a solver.
+static WARNINGS: Mutex<Vec<String>> = Mutex::new(vec![]);
+
+pub fn warn(message: String) {
+  WARNINGS.lock().unwrap().push(message);
+}
+
+// the warnings so far, which are then forgotten about
+pub fn take_warnings() -> Vec<String> {
+  std::mem::take(&mut *WARNINGS.lock().unwrap())
+}
+
+// The events as a readable story, with the more detailed events indented under the less detailed ones. At the
+// highest level of detail the values attached to each event are shown too.
+pub fn narrative(events: &[Event]) -> String {
+  let show_fields = enabled(Level::Everything);
+  let mut lines = vec![];
+  for event in events {
+    let indent = "  ".repeat(event.level as usize);
+    let mut line = format!("{}{}", indent, event.message);
+    if show_fields && !event.fields.is_empty() {
+      let fields: Vec<String> = event.fields.iter().map(|(name, value)| format!("{}={}", name, value)).collect();
+      line.push_str(&format!("  [{}: {}]", event.kind, fields.join(", ")));
+    }
+    lines.push(line);
+  }
+  lines.join("\n")
+}
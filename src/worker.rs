@@ -0,0 +1,93 @@
+use std::env;
+use std::io::{ErrorKind, Read, Write};
+use std::process::{Command, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+// Runs a solver in a separate copy of this program, because a thread that is stuck in an infinite loop can't
+// be stopped, whereas a process can just be killed once the time is up. The `args` are the command line for
+// one of the hidden worker commands, which read the puzzle input from stdin and print the answer.
+
+// the result of running one solver on one input. Solvers signal bad input (or bugs!) by panicking, and some
+// of the faster approaches can loop forever on inputs they weren't designed for, so both of those have to be
+// treated as outcomes in their own right.
+#[derive(PartialEq, Clone, Debug)]
+pub enum Outcome {
+  Answer(String),
+  // the location is the file, line and column the panic happened at
+  Panicked { location: String, message: String },
+  TimedOut,
+}
+
+impl Outcome {
+  pub fn describe(&self) -> String {
+    match self {
+      Outcome::Answer(answer) => answer.clone(),
+      Outcome::Panicked { location, message } => format!("panicked at {}: {}", location, message),
+      Outcome::TimedOut => String::from("timed out"),
+    }
+  }
+}
+
+pub fn run_in_worker(args: &[&str], input: &str, timeout: Duration) -> Outcome {
+  let mut child = Command::new(env::current_exe().unwrap())
+    .args(args)
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .unwrap();
+  // The input goes in from a thread of its own, since a big one fills the pipe and the write would wait for the
+  // worker to read it - which it might never do, if it's stuck or has already panicked. A worker that exits
+  // without reading all its input closes the pipe, which is fine: how it exited is what matters.
+  let mut stdin = child.stdin.take().unwrap();
+  let input = input.to_owned();
+  let writer = thread::spawn(move || match stdin.write_all(input.as_bytes()) {
+    Err(error) if error.kind() != ErrorKind::BrokenPipe => panic!("couldn't write the input to the worker: {}", error),
+    _ => (),
+  });
+
+  // The output is read as it comes for the same reason: a worker that prints more than the pipe holds (a big
+  // --explain, say) would otherwise wait for it to be read until the time ran out.
+  let stdout = read_in_background(child.stdout.take().unwrap());
+  let stderr = read_in_background(child.stderr.take().unwrap());
+
+  let start = Instant::now();
+  let status = loop {
+    if let Some(status) = child.try_wait().unwrap() {
+      break status;
+    }
+    if start.elapsed() > timeout {
+      child.kill().unwrap();
+      child.wait().unwrap();
+      let _ = writer.join();
+      return Outcome::TimedOut;
+    }
+    thread::sleep(Duration::from_millis(1));
+  };
+  writer.join().unwrap();
+
+  let stdout = stdout.join().unwrap();
+  if status.success() {
+    Outcome::Answer(String::from_utf8_lossy(&stdout).into_owned())
+  } else {
+    // the default panic hook prints "thread '...' panicked at <location>:", with the message on the next line
+    let stderr = String::from_utf8_lossy(&stderr.join().unwrap()).into_owned();
+    let mut lines = stderr.lines().skip_while(|line| !line.contains("panicked at "));
+    let location = lines
+      .next()
+      .and_then(|line| line.split("panicked at ").nth(1))
+      .map_or(String::from("unknown location"), |location| location.trim_end_matches(':').to_owned());
+    let message = lines.next().unwrap_or("unknown panic").to_owned();
+    Outcome::Panicked { location, message }
+  }
+}
+
+// everything written to the pipe until it's closed, read from a thread of its own
+fn read_in_background(mut pipe: impl Read + Send + 'static) -> JoinHandle<Vec<u8>> {
+  thread::spawn(move || {
+    let mut bytes = vec![];
+    let _ = pipe.read_to_end(&mut bytes);
+    bytes
+  })
+}